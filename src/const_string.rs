@@ -52,7 +52,12 @@ impl ConstString {
     }
 
     pub(crate) const fn as_str(&self) -> &str {
-        unsafe { std::str::from_utf8_unchecked(self.buf.as_ref()) }
+        unsafe {
+            std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+                self.buf.as_mut_ptr(),
+                self.size,
+            ))
+        }
     }
 
     pub(crate) const fn reserve(&mut self, additional: usize) {
@@ -5,6 +5,11 @@ use std::{
 
 use crate::const_alloc::ConstAlloc;
 
+/// A growable array usable in const contexts.
+///
+/// Like [`ConstString`](crate::const_string::ConstString) it never frees its
+/// buffer, so that it can be dropped in const contexts, and it's meant for
+/// `Copy` items that don't need dropping either.
 pub(crate) struct ConstVec<T> {
     buf: *mut MaybeUninit<T>,
     size: usize,
@@ -13,57 +18,16 @@ pub(crate) struct ConstVec<T> {
 
 impl<T> const Default for ConstVec<T> {
     fn default() -> Self {
-        let Ok(layout) = Layout::array::<T>(16) else {
-            panic!();
-        };
-
         Self {
-            buf: if layout.size() > 0 {
-                let Ok(ptr) = ConstAlloc.allocate(layout) else {
-                    panic!();
-                };
-
-                ptr.as_mut_ptr() as *mut _
-            } else {
-                core::ptr::null_mut()
-            },
+            buf: core::ptr::null_mut(),
             size: 0,
             cap: 0,
         }
     }
 }
 
-impl<T> Drop for ConstVec<T> {
-    fn drop(&mut self) {
-        let mut idx = 0;
-
-        // FIXME: deal with panics in Drop of T.
-        unsafe {
-            while idx < self.size {
-                core::ptr::drop_in_place(self.buf.add(idx));
-                idx += 1;
-            }
-            
-            let Some(ptr) = NonNull::new(self.buf as *mut u8) else { return; };
-
-            ConstAlloc.deallocate(
-                ptr,
-                Layout::from_size_align_unchecked(self.cap, core::mem::align_of::<T>()),
-            )
-        }
-    }
-}
-
+#[allow(dead_code)]
 impl<T> ConstVec<T> {
-    //pub(crate) const fn push_str(&mut self, str: &str) {
-    //    self.reserve(str.len());
-
-    //    unsafe {
-    //        self.end().copy_from_nonoverlapping(str.as_ptr(), str.len());
-    //        self.size += str.len();
-    //    }
-    //}
-
     pub(crate) const fn push(&mut self, item: T) {
         self.reserve(1);
 
@@ -73,16 +37,26 @@ impl<T> ConstVec<T> {
         }
     }
 
+    pub(crate) const fn len(&self) -> usize {
+        self.size
+    }
+
+    pub(crate) const fn as_slice(&self) -> &[T] {
+        if self.buf.is_null() {
+            return &[];
+        }
+
+        unsafe { core::slice::from_raw_parts(self.buf as *const _, self.size) }
+    }
+
     pub(crate) const fn leak(mut self) -> &'static [T] {
         self.shrink_to_size();
 
-        unsafe { 
-            if self.buf.is_null() {
-                panic!()
-            }
-
-            core::slice::from_raw_parts(self.buf as *const _, self.size)
+        if self.buf.is_null() {
+            return &[];
         }
+
+        unsafe { core::slice::from_raw_parts(self.buf as *const _, self.size) }
     }
 
     pub(crate) const fn reserve(&mut self, additional: usize) {
@@ -91,15 +65,14 @@ impl<T> ConstVec<T> {
             return;
         }
 
-        self.buf = unsafe {
-            let old_layout = Layout::from_size_align_unchecked(self.cap, std::mem::align_of::<T>());
-            let new_layout = Layout::from_size_align_unchecked(next_cap, std::mem::align_of::<T>());
-
+        let (Ok(old_layout), Ok(new_layout)) = (Layout::array::<T>(self.cap), Layout::array::<T>(next_cap)) else {
+            panic!();
+        };
 
-            let alloc = if let Some(ptr) = NonNull::new(self.buf as *mut _) {
-                ConstAlloc.grow(ptr, old_layout, new_layout)
-            } else {
-                ConstAlloc.allocate(new_layout)
+        self.buf = unsafe {
+            let alloc = match NonNull::new(self.buf as *mut u8) {
+                Some(ptr) if old_layout.size() > 0 => ConstAlloc.grow(ptr, old_layout, new_layout),
+                _ => ConstAlloc.allocate(new_layout),
             };
 
             match alloc {
@@ -107,6 +80,7 @@ impl<T> ConstVec<T> {
                 Err(_) => handle_alloc_error(new_layout),
             }
         };
+        self.cap = next_cap;
     }
 
     const fn end(&mut self) -> *mut MaybeUninit<T> {
@@ -114,17 +88,21 @@ impl<T> ConstVec<T> {
     }
 
     const fn shrink_to_size(&mut self) {
-        self.buf = unsafe {
-            let align = std::mem::align_of::<T>();
-            let old_layout = Layout::from_size_align_unchecked(self.cap, align);
-            let new_layout = Layout::from_size_align_unchecked(self.size, align);
+        let (Ok(old_layout), Ok(new_layout)) = (Layout::array::<T>(self.cap), Layout::array::<T>(self.size)) else {
+            panic!();
+        };
 
-            let Some(ptr) = NonNull::new(self.buf as *mut _) else { return; };
+        let Some(ptr) = NonNull::new(self.buf as *mut u8) else { return; };
+        if new_layout.size() == 0 || old_layout.size() == new_layout.size() {
+            return;
+        }
 
+        self.buf = unsafe {
             match ConstAlloc.shrink(ptr, old_layout, new_layout) {
                 Ok(m) => m.as_mut_ptr() as *mut _,
                 Err(_) => handle_alloc_error(new_layout),
             }
         };
+        self.cap = self.size;
     }
 }
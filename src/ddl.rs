@@ -0,0 +1,745 @@
+//! Data definition statements, creating the tables declared in
+//! [`schema`](crate::schema), as in `CREATE TABLE ...`.
+//!
+//! Column types, `NOT NULL`, defaults and generated values come from the
+//! [`Column`] metadata, while table constraints are added to the statement:
+//!
+//! ```ignore
+//! create_table(USERS)
+//!     .if_not_exists()
+//!     .primary_key(USERS_ID)
+//!     .unique(USERS_EMAIL)
+//! ```
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{check_foreign_key, str_eq, Sql, SqlExpression},
+    schema::{Column, ColumnDefault, ColumnNames, Generated, SqlType, Table},
+};
+
+/// A `CREATE TABLE` statement for `table`, defining all of its columns.
+///
+/// Table constraints are added by [`CreateTable`] methods, which panic when
+/// given columns of another table.
+pub const fn create_table(table: Table) -> CreateTable {
+    CreateTable {
+        table,
+        if_not_exists: false,
+        primary_key: None,
+        constraints: None,
+    }
+}
+
+/// `CREATE TABLE [IF NOT EXISTS] table (columns,constraints)`.
+pub struct CreateTable<Constraints = ()> {
+    table: Table,
+    if_not_exists: bool,
+    /// The primary key column, when it has exactly one.
+    primary_key: Option<Column>,
+    constraints: Option<Constraints>,
+}
+
+impl<Constraints> CreateTable<Constraints> {
+    /// Skips the creation when the table already exists.
+    pub const fn if_not_exists(mut self) -> Self {
+        self.if_not_exists = true;
+        self
+    }
+
+    /// Identifies the rows by `columns`.
+    ///
+    /// SQLite only generates identities for a primary key of a single integer
+    /// column, so rendering any other generated identity for SQLite panics.
+    pub const fn primary_key<Cols>(
+        mut self,
+        columns: Cols,
+    ) -> CreateTable<TableConstraints<Constraints, PrimaryKey<Cols>>>
+    where
+        Self: ~const Destruct,
+        Cols: ~const ColumnNames,
+    {
+        self.check_columns(&columns);
+        self.primary_key = columns.single_column();
+        self.constraint(PrimaryKey(columns))
+    }
+
+    pub const fn unique<Cols>(
+        self,
+        columns: Cols,
+    ) -> CreateTable<TableConstraints<Constraints, Unique<Cols>>>
+    where
+        Self: ~const Destruct,
+        Cols: ~const ColumnNames,
+    {
+        self.check_columns(&columns);
+        self.constraint(Unique(columns))
+    }
+
+    /// Rejects the rows not satisfying `predicate`.
+    ///
+    /// Columns should be given by their bare names, see
+    /// [`Column::unqualified`].
+    pub const fn check<Pred>(
+        self,
+        predicate: Pred,
+    ) -> CreateTable<TableConstraints<Constraints, Check<Pred>>>
+    where
+        Self: ~const Destruct,
+    {
+        self.constraint(Check(predicate))
+    }
+
+    /// Requires `columns` to match `referenced` columns of a row in `table`.
+    ///
+    /// Both lists must have as many columns, which is checked at compile time
+    /// when known from their types.
+    pub const fn foreign_key<Cols, Refs>(
+        self,
+        columns: Cols,
+        table: Table,
+        referenced: Refs,
+    ) -> CreateTable<TableConstraints<Constraints, ForeignKey<Cols, Refs>>>
+    where
+        Self: ~const Destruct,
+        Cols: ~const ColumnNames,
+        Refs: ~const ColumnNames,
+        [(); check_foreign_key(Cols::ARITY, Refs::ARITY)]:,
+    {
+        self.check_columns(&columns);
+
+        if !referenced.belong_to(&table) {
+            panic!("the referenced columns must belong to the referenced table");
+        }
+
+        self.constraint(ForeignKey {
+            columns,
+            table,
+            referenced,
+        })
+    }
+
+    const fn check_columns<Cols>(&self, columns: &Cols)
+    where
+        Cols: ~const ColumnNames,
+    {
+        if !columns.belong_to(&self.table) {
+            panic!("constrained columns must belong to the created table");
+        }
+    }
+
+    const fn constraint<C>(self, constraint: C) -> CreateTable<TableConstraints<Constraints, C>>
+    where
+        Self: ~const Destruct,
+    {
+        CreateTable {
+            constraints: Some(TableConstraints {
+                prev: self.constraints,
+                constraint,
+            }),
+            ..self
+        }
+    }
+}
+
+impl<Constraints> const SqlExpression for CreateTable<Constraints>
+where
+    Constraints: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("CREATE TABLE ");
+
+        if self.if_not_exists {
+            if matches!(sql.dialect(), Dialect::SqlServer | Dialect::Ansi) {
+                panic!("IF NOT EXISTS is not supported by SQL Server and ANSI SQL");
+            }
+
+            sql.push_str("IF NOT EXISTS ");
+        }
+
+        self.table.write_sql_expression(sql);
+        sql.push_str(" (");
+
+        let mut columns = self.table.all_columns;
+        while let Some((cur, rest)) = columns.split_first() {
+            write_column_definition(sql, cur, &self.primary_key);
+
+            if !rest.is_empty() {
+                sql.comma();
+            }
+
+            columns = rest;
+        }
+
+        if let Some(constraints) = &self.constraints {
+            constraints.write_sql_expression(sql.comma());
+        }
+
+        sql.push(b')');
+    }
+}
+
+/// `name type [GENERATED ...] [DEFAULT ...] [NOT NULL]`.
+const fn write_column_definition(sql: &mut Sql, column: &Column, primary_key: &Option<Column>) {
+    let dialect = sql.dialect();
+    sql.push_ident(column.name);
+
+    if matches!(
+        (dialect, column.generated),
+        (
+            Dialect::Sqlite,
+            Some(Generated::AlwaysAsIdentity | Generated::ByDefaultAsIdentity)
+        )
+    ) && !is_rowid_alias(column, primary_key)
+    {
+        panic!("SQLite only generates identities for a single INTEGER PRIMARY KEY column");
+    }
+
+    // SQL Server computed columns have no declared type.
+    if !matches!(
+        (dialect, column.generated),
+        (
+            Dialect::SqlServer,
+            Some(Generated::Stored(_) | Generated::Virtual(_))
+        )
+    ) {
+        column.ty.write_sql_expression(sql.spacing());
+    }
+
+    if let Some(generated) = column.generated {
+        write_generated(sql, generated);
+    }
+
+    if let Some(default) = column.default {
+        sql.push_str(" DEFAULT ");
+
+        // MySQL only accepts expressions other than literals and
+        // `CURRENT_TIMESTAMP` within parentheses.
+        if matches!(
+            (dialect, default),
+            (
+                Dialect::MySql,
+                ColumnDefault::CurrentDate | ColumnDefault::Expr(_)
+            )
+        ) {
+            sql.push(b'(');
+            default.write_sql_expression(sql);
+            sql.push(b')');
+        } else {
+            default.write_sql_expression(sql);
+        }
+    }
+
+    if !column.nullable {
+        sql.push_str(" NOT NULL");
+    }
+}
+
+/// Whether `column` is the SQLite `INTEGER PRIMARY KEY`, which aliases the
+/// row id and so is generated.
+const fn is_rowid_alias(column: &Column, primary_key: &Option<Column>) -> bool {
+    let Some(primary_key) = primary_key else {
+        return false;
+    };
+
+    str_eq(primary_key.name, column.name)
+        && matches!(
+            column.ty,
+            SqlType::SmallInt | SqlType::Integer | SqlType::BigInt
+        )
+}
+
+const fn write_generated(sql: &mut Sql, generated: Generated) {
+    match (sql.dialect(), generated) {
+        // Integer primary keys are generated by SQLite.
+        (Dialect::Sqlite, Generated::AlwaysAsIdentity | Generated::ByDefaultAsIdentity) => {}
+        (Dialect::MySql, Generated::AlwaysAsIdentity | Generated::ByDefaultAsIdentity) => {
+            sql.push_str(" AUTO_INCREMENT");
+        }
+        (Dialect::SqlServer, Generated::AlwaysAsIdentity | Generated::ByDefaultAsIdentity) => {
+            sql.push_str(" IDENTITY(1,1)");
+        }
+        (_, Generated::AlwaysAsIdentity) => {
+            sql.push_str(" GENERATED ALWAYS AS IDENTITY");
+        }
+        (_, Generated::ByDefaultAsIdentity) => {
+            sql.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+        }
+        (Dialect::SqlServer, Generated::Stored(expr)) => {
+            sql.push_str(" AS (").push_str(expr).push_str(") PERSISTED");
+        }
+        (Dialect::SqlServer, Generated::Virtual(expr)) => {
+            sql.push_str(" AS (").push_str(expr).push(b')');
+        }
+        (Dialect::Postgres, Generated::Virtual(_)) => {
+            panic!("virtual generated columns are not supported by Postgres");
+        }
+        (_, Generated::Stored(expr)) => {
+            sql.push_str(" GENERATED ALWAYS AS (")
+                .push_str(expr)
+                .push_str(") STORED");
+        }
+        (Dialect::Ansi, Generated::Virtual(expr)) => {
+            sql.push_str(" GENERATED ALWAYS AS (")
+                .push_str(expr)
+                .push(b')');
+        }
+        (_, Generated::Virtual(expr)) => {
+            sql.push_str(" GENERATED ALWAYS AS (")
+                .push_str(expr)
+                .push_str(") VIRTUAL");
+        }
+    }
+}
+
+/// The closest type to `self` in the dialect.
+impl const SqlExpression for SqlType {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let dialect = sql.dialect();
+
+        match (*self, dialect) {
+            // Only `INTEGER` primary keys alias the row id.
+            (SqlType::SmallInt | SqlType::Integer | SqlType::BigInt, Dialect::Sqlite) => {
+                sql.push_str("INTEGER");
+            }
+            (SqlType::SmallInt, _) => {
+                sql.push_str("SMALLINT");
+            }
+            (SqlType::Integer, Dialect::MySql | Dialect::SqlServer) => {
+                sql.push_str("INT");
+            }
+            (SqlType::Integer, _) => {
+                sql.push_str("INTEGER");
+            }
+            (SqlType::BigInt, _) => {
+                sql.push_str("BIGINT");
+            }
+            (SqlType::Real, Dialect::MySql) => {
+                sql.push_str("FLOAT");
+            }
+            (SqlType::Real, _) | (SqlType::Double, Dialect::Sqlite) => {
+                sql.push_str("REAL");
+            }
+            (SqlType::Double, Dialect::MySql) => {
+                sql.push_str("DOUBLE");
+            }
+            (SqlType::Double, Dialect::SqlServer) => {
+                sql.push_str("FLOAT");
+            }
+            (SqlType::Double, _) => {
+                sql.push_str("DOUBLE PRECISION");
+            }
+            (SqlType::Numeric(..), Dialect::Sqlite) => {
+                sql.push_str("NUMERIC");
+            }
+            (SqlType::Numeric(precision, scale), Dialect::MySql | Dialect::SqlServer) => {
+                sql.push_str("DECIMAL(")
+                    .push_u64(precision as u64)
+                    .comma()
+                    .push_u64(scale as u64)
+                    .push(b')');
+            }
+            (SqlType::Numeric(precision, scale), _) => {
+                sql.push_str("NUMERIC(")
+                    .push_u64(precision as u64)
+                    .comma()
+                    .push_u64(scale as u64)
+                    .push(b')');
+            }
+            (SqlType::Boolean, Dialect::SqlServer) => {
+                sql.push_str("BIT");
+            }
+            (SqlType::Boolean, _) => {
+                sql.push_str("BOOLEAN");
+            }
+            (SqlType::Text | SqlType::Json | SqlType::Jsonb, Dialect::SqlServer) => {
+                sql.push_str("NVARCHAR(MAX)");
+            }
+            (SqlType::Text, Dialect::Ansi) => {
+                sql.push_str("CLOB");
+            }
+            (SqlType::Text, _) => {
+                sql.push_str("TEXT");
+            }
+            (SqlType::Varchar(len), Dialect::SqlServer) => {
+                sql.push_str("NVARCHAR(").push_u64(len as u64).push(b')');
+            }
+            (SqlType::Varchar(len), _) => {
+                sql.push_str("VARCHAR(").push_u64(len as u64).push(b')');
+            }
+            (SqlType::Char(len), Dialect::SqlServer) => {
+                sql.push_str("NCHAR(").push_u64(len as u64).push(b')');
+            }
+            (SqlType::Char(len), _) => {
+                sql.push_str("CHAR(").push_u64(len as u64).push(b')');
+            }
+            (SqlType::Date, _) => {
+                sql.push_str("DATE");
+            }
+            (SqlType::Time, _) => {
+                sql.push_str("TIME");
+            }
+            (SqlType::Timestamp, Dialect::MySql) => {
+                sql.push_str("DATETIME");
+            }
+            (SqlType::Timestamp, Dialect::SqlServer) => {
+                sql.push_str("DATETIME2");
+            }
+            (SqlType::Timestamp, _) => {
+                sql.push_str("TIMESTAMP");
+            }
+            (SqlType::TimestampTz, Dialect::Postgres) => {
+                sql.push_str("TIMESTAMPTZ");
+            }
+            (SqlType::TimestampTz, Dialect::MySql) => {
+                sql.push_str("TIMESTAMP");
+            }
+            (SqlType::TimestampTz, Dialect::SqlServer) => {
+                sql.push_str("DATETIMEOFFSET");
+            }
+            (SqlType::TimestampTz, _) => {
+                sql.push_str("TIMESTAMP WITH TIME ZONE");
+            }
+            (SqlType::Uuid, Dialect::Postgres) => {
+                sql.push_str("UUID");
+            }
+            (SqlType::Uuid, Dialect::SqlServer) => {
+                sql.push_str("UNIQUEIDENTIFIER");
+            }
+            (SqlType::Uuid, _) => {
+                sql.push_str("CHAR(36)");
+            }
+            (SqlType::Json | SqlType::Jsonb, Dialect::Sqlite) => {
+                sql.push_str("TEXT");
+            }
+            (SqlType::Jsonb, Dialect::Postgres) => {
+                sql.push_str("JSONB");
+            }
+            (SqlType::Json | SqlType::Jsonb, _) => {
+                sql.push_str("JSON");
+            }
+            (SqlType::Bytea, Dialect::Postgres) => {
+                sql.push_str("BYTEA");
+            }
+            (SqlType::Bytea, Dialect::MySql) => {
+                sql.push_str("LONGBLOB");
+            }
+            (SqlType::Bytea, Dialect::SqlServer) => {
+                sql.push_str("VARBINARY(MAX)");
+            }
+            (SqlType::Bytea, _) => {
+                sql.push_str("BLOB");
+            }
+            (SqlType::Array(element), Dialect::Postgres) => {
+                element.write_sql_expression(sql);
+                sql.push_str("[]");
+            }
+            (SqlType::Enum(name), Dialect::Postgres) => {
+                sql.push_ident(name);
+            }
+            (SqlType::Array(_), _) => {
+                panic!("array columns are only supported by Postgres");
+            }
+            (SqlType::Enum(_), _) => {
+                panic!("enum columns are only supported by Postgres");
+            }
+        }
+    }
+}
+
+/// The table constraints of a [`CreateTable`], one per constraint method call.
+pub struct TableConstraints<Prev, C> {
+    prev: Option<Prev>,
+    constraint: C,
+}
+
+impl<Prev, C> const SqlExpression for TableConstraints<Prev, C>
+where
+    Prev: ~const SqlExpression,
+    C: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if let Some(prev) = &self.prev {
+            prev.write_sql_expression(sql);
+            sql.comma();
+        }
+
+        self.constraint.write_sql_expression(sql);
+    }
+}
+
+/// `PRIMARY KEY (columns)`.
+pub struct PrimaryKey<Cols>(Cols);
+
+impl<Cols> const SqlExpression for PrimaryKey<Cols>
+where
+    Cols: ~const ColumnNames,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("PRIMARY KEY (");
+        self.0.write_column_names(sql);
+        sql.push(b')');
+    }
+}
+
+/// `UNIQUE (columns)`.
+pub struct Unique<Cols>(Cols);
+
+impl<Cols> const SqlExpression for Unique<Cols>
+where
+    Cols: ~const ColumnNames,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("UNIQUE (");
+        self.0.write_column_names(sql);
+        sql.push(b')');
+    }
+}
+
+/// `CHECK (predicate)`.
+pub struct Check<Pred>(Pred);
+
+impl<Pred> const SqlExpression for Check<Pred>
+where
+    Pred: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("CHECK (");
+        self.0.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// `FOREIGN KEY (columns) REFERENCES table (referenced)`.
+pub struct ForeignKey<Cols, Refs> {
+    columns: Cols,
+    table: Table,
+    referenced: Refs,
+}
+
+impl<Cols, Refs> const SqlExpression for ForeignKey<Cols, Refs>
+where
+    Cols: ~const ColumnNames,
+    Refs: ~const ColumnNames,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("FOREIGN KEY (");
+        self.columns.write_column_names(sql);
+        sql.push_str(") REFERENCES ");
+        self.table.write_sql_expression(sql);
+        sql.push_str(" (");
+        self.referenced.write_column_names(sql);
+        sql.push(b')');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{expression::ops::SqlExpressionExt, schema::fixtures::*};
+
+    crate::table! {
+        accounts in billing {
+            id: BigInt.not_null().generated(AlwaysAsIdentity),
+            owner_id: BigInt.not_null(),
+            balance: Numeric(12, 2).not_null().default(Int(0)),
+            currency: Char(3).not_null().default(Str("EUR")),
+            opened_at: TimestampTz.not_null().default(CurrentTimestamp),
+            cents: BigInt.generated(Stored("balance * 100")),
+        }
+    }
+
+    crate::table! {
+        entries in billing {
+            account_id: BigInt.not_null(),
+            line: Integer.not_null().generated(ByDefaultAsIdentity),
+            amount: Numeric(12, 2).not_null(),
+            cents: BigInt.generated(Virtual("amount * 100")),
+        }
+    }
+
+    macro_rules! create_accounts {
+        () => {
+            create_table(accounts)
+                .primary_key(accounts::id)
+                .unique((accounts::owner_id, accounts::currency))
+                .check(accounts::balance.unqualified().ge(0))
+                .foreign_key(accounts::owner_id, USERS, USERS_ID)
+        };
+    }
+
+    #[test]
+    fn test_create_table() {
+        static SQL: [&str; 3] = [
+            create_accounts!().if_not_exists().to_sql().into_str(),
+            create_accounts!()
+                .to_sql_with(Dialect::SqlServer)
+                .into_str(),
+            create_accounts!()
+                .if_not_exists()
+                .to_sql_with(Dialect::MySql)
+                .into_str(),
+        ];
+
+        assert_eq!(
+            SQL,
+            [
+                "CREATE TABLE IF NOT EXISTS billing.accounts (\
+                 id BIGINT GENERATED ALWAYS AS IDENTITY NOT NULL,\
+                 owner_id BIGINT NOT NULL,\
+                 balance NUMERIC(12,2) DEFAULT 0 NOT NULL,\
+                 currency CHAR(3) DEFAULT 'EUR' NOT NULL,\
+                 opened_at TIMESTAMPTZ DEFAULT CURRENT_TIMESTAMP NOT NULL,\
+                 cents BIGINT GENERATED ALWAYS AS (balance * 100) STORED,\
+                 PRIMARY KEY (id),UNIQUE (owner_id,currency),CHECK (balance >= 0),\
+                 FOREIGN KEY (owner_id) REFERENCES public.users (id))",
+                "CREATE TABLE billing.accounts (\
+                 id BIGINT IDENTITY(1,1) NOT NULL,\
+                 owner_id BIGINT NOT NULL,\
+                 balance DECIMAL(12,2) DEFAULT 0 NOT NULL,\
+                 currency NCHAR(3) DEFAULT 'EUR' NOT NULL,\
+                 opened_at DATETIMEOFFSET DEFAULT CURRENT_TIMESTAMP NOT NULL,\
+                 cents AS (balance * 100) PERSISTED,\
+                 PRIMARY KEY (id),UNIQUE (owner_id,currency),CHECK (balance >= 0),\
+                 FOREIGN KEY (owner_id) REFERENCES [public].users (id))",
+                "CREATE TABLE IF NOT EXISTS billing.accounts (\
+                 id BIGINT AUTO_INCREMENT NOT NULL,\
+                 owner_id BIGINT NOT NULL,\
+                 balance DECIMAL(12,2) DEFAULT 0 NOT NULL,\
+                 currency CHAR(3) DEFAULT 'EUR' NOT NULL,\
+                 opened_at TIMESTAMP DEFAULT CURRENT_TIMESTAMP NOT NULL,\
+                 cents BIGINT GENERATED ALWAYS AS (balance * 100) STORED,\
+                 PRIMARY KEY (id),UNIQUE (owner_id,currency),CHECK (balance >= 0),\
+                 FOREIGN KEY (owner_id) REFERENCES public.users (id))",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_column_types() {
+        static TYPES: [&str; 3] = {
+            let types = (
+                SqlType::SmallInt,
+                SqlType::Double,
+                SqlType::Boolean,
+                SqlType::Varchar(80),
+                SqlType::Timestamp,
+                SqlType::Uuid,
+                SqlType::Jsonb,
+                SqlType::Bytea,
+            );
+
+            [
+                types.to_sql().into_str(),
+                types.to_sql_with(Dialect::Sqlite).into_str(),
+                types.to_sql_with(Dialect::SqlServer).into_str(),
+            ]
+        };
+        static ARRAY: &str = SqlType::Array(&SqlType::Enum("mood")).to_sql().into_str();
+
+        assert_eq!(
+            TYPES,
+            [
+                "SMALLINT,DOUBLE PRECISION,BOOLEAN,VARCHAR(80),TIMESTAMP,UUID,JSONB,BYTEA",
+                "INTEGER,REAL,BOOLEAN,VARCHAR(80),TIMESTAMP,CHAR(36),TEXT,BLOB",
+                "SMALLINT,FLOAT,BIT,NVARCHAR(80),DATETIME2,UNIQUEIDENTIFIER,NVARCHAR(MAX),\
+                 VARBINARY(MAX)",
+            ]
+        );
+        assert_eq!(ARRAY, "mood[]");
+    }
+
+    #[test]
+    #[should_panic(expected = "IF NOT EXISTS is not supported by SQL Server and ANSI SQL")]
+    fn test_if_not_exists_unsupported() {
+        create_table(USERS)
+            .if_not_exists()
+            .to_sql_with(Dialect::SqlServer);
+    }
+
+    #[test]
+    fn test_create_table_sqlite_and_ansi() {
+        static SQL: [&str; 2] = [
+            create_accounts!()
+                .if_not_exists()
+                .to_sql_with(Dialect::Sqlite)
+                .into_str(),
+            create_accounts!().to_sql_with(Dialect::Ansi).into_str(),
+        ];
+        static VIRTUAL: &str = create_table(entries)
+            .primary_key((entries::account_id, entries::line))
+            .to_sql_with(Dialect::Ansi)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            [
+                "CREATE TABLE IF NOT EXISTS billing.accounts (\
+                 id INTEGER NOT NULL,\
+                 owner_id INTEGER NOT NULL,\
+                 balance NUMERIC DEFAULT 0 NOT NULL,\
+                 currency CHAR(3) DEFAULT 'EUR' NOT NULL,\
+                 opened_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,\
+                 cents INTEGER GENERATED ALWAYS AS (balance * 100) STORED,\
+                 PRIMARY KEY (id),UNIQUE (owner_id,currency),CHECK (balance >= 0),\
+                 FOREIGN KEY (owner_id) REFERENCES public.users (id))",
+                "CREATE TABLE billing.accounts (\
+                 id BIGINT GENERATED ALWAYS AS IDENTITY NOT NULL,\
+                 owner_id BIGINT NOT NULL,\
+                 balance NUMERIC(12,2) DEFAULT 0 NOT NULL,\
+                 currency CHAR(3) DEFAULT 'EUR' NOT NULL,\
+                 opened_at TIMESTAMP WITH TIME ZONE DEFAULT CURRENT_TIMESTAMP NOT NULL,\
+                 cents BIGINT GENERATED ALWAYS AS (balance * 100) STORED,\
+                 PRIMARY KEY (id),UNIQUE (owner_id,currency),CHECK (balance >= 0),\
+                 FOREIGN KEY (owner_id) REFERENCES public.users (id))",
+            ]
+        );
+        assert_eq!(
+            VIRTUAL,
+            "CREATE TABLE billing.entries (\
+             account_id BIGINT NOT NULL,\
+             line INTEGER GENERATED BY DEFAULT AS IDENTITY NOT NULL,\
+             amount NUMERIC(12,2) NOT NULL,\
+             cents BIGINT GENERATED ALWAYS AS (amount * 100),\
+             PRIMARY KEY (account_id,line))"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "virtual generated columns are not supported by Postgres")]
+    fn test_virtual_column_postgres() {
+        create_table(entries)
+            .primary_key((entries::account_id, entries::line))
+            .to_sql();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "SQLite only generates identities for a single INTEGER PRIMARY KEY column"
+    )]
+    fn test_sqlite_identity_in_composite_key() {
+        create_table(entries)
+            .primary_key((entries::account_id, entries::line))
+            .to_sql_with(Dialect::Sqlite);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "SQLite only generates identities for a single INTEGER PRIMARY KEY column"
+    )]
+    fn test_sqlite_identity_without_primary_key() {
+        create_table(accounts).to_sql_with(Dialect::Sqlite);
+    }
+
+    #[test]
+    #[should_panic(expected = "constrained columns must belong to the created table")]
+    fn test_primary_key_of_other_table() {
+        create_table(accounts).primary_key(USERS_ID);
+    }
+
+    #[test]
+    #[should_panic(expected = "the referenced columns must belong to the referenced table")]
+    fn test_foreign_key_to_other_table() {
+        create_table(accounts).foreign_key(accounts::owner_id, USERS, POSTS_ID);
+    }
+}
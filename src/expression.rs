@@ -1,13 +1,74 @@
 use std::mem::{ManuallyDrop, MaybeUninit};
 
-use crate::const_string::ConstString;
+use crate::{
+    const_string::ConstString,
+    const_vec::ConstVec,
+    dialect::{Dialect, Quoting},
+    schema::SqlTypeClass,
+};
+
+pub mod aggregate;
+pub mod alias;
+pub mod literal;
+pub mod ops;
+pub mod order;
+pub mod param;
+pub mod window;
 
 #[const_trait]
 pub trait SqlExpression {
+    /// How tightly the outermost operator of this expression binds.
+    ///
+    /// Operators use it to decide whether an operand must be parenthesized.
+    const PRECEDENCE: Precedence = Precedence::Atom;
+
+    /// The class of the SQL type of this expression, which operators and
+    /// assignments require to be compatible at compile time.
+    ///
+    /// Expressions of unknown class are never rejected.
+    const CLASS: SqlTypeClass = SqlTypeClass::Unknown;
+
+    /// Whether the value of this expression may be `NULL`, which assignments
+    /// to `NOT NULL` columns reject at compile time.
+    ///
+    /// For sources, whether some of their columns may be `NULL` for being on
+    /// the outer side of a join. Expressions not known to be nullable are
+    /// never rejected.
+    const NULLABLE: bool = false;
+
+    /// How many columns this expression yields when used as a projection, or
+    /// `None` when it isn't known from its type, as with `*` or slices.
+    const ARITY: Option<usize> = Some(1);
+
+    /// The class of each of the columns this expression yields as a
+    /// projection, one per element for tuples, or empty when unknown.
+    const CLASSES: &'static [SqlTypeClass] = &[Self::CLASS];
+
     fn write_sql_expression(&self, sql: &mut Sql);
 
+    /// Writes this expression where a search condition is expected, as in
+    /// `WHERE` or `ON`, which only differs for boolean literals.
+    fn write_sql_predicate(&self, sql: &mut Sql) {
+        self.write_sql_expression(sql);
+    }
+
+    /// Like [`SqlExpression::ARITY`], also counting the columns of slices.
+    fn arity(&self) -> Option<usize> {
+        Self::ARITY
+    }
+
+    /// Whether this is a query with its own `ORDER BY`, `LIMIT` or `OFFSET`,
+    /// which must be parenthesized to be combined with other queries.
+    fn is_ordered_or_limited(&self) -> bool {
+        false
+    }
+
     fn to_sql(&self) -> Sql {
-        let mut sql = Sql::default();
+        self.to_sql_with(Dialect::default())
+    }
+
+    fn to_sql_with(&self, dialect: Dialect) -> Sql {
+        let mut sql = Sql::new(dialect);
 
         self.write_sql_expression(&mut sql);
 
@@ -22,27 +83,92 @@ impl const SqlExpression for Sql {
     }
 }
 
+impl const SqlExpression for () {
+    const ARITY: Option<usize> = Some(0);
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
+    #[inline(always)]
+    fn write_sql_expression(&self, _: &mut Sql) {}
+}
+
+/// `*`, as in `SELECT *` or `COUNT(*)`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Star;
+
+impl const SqlExpression for Star {
+    const ARITY: Option<usize> = None;
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push(b'*');
+    }
+}
+
 impl<S: ~const SqlExpression, const N: usize> const SqlExpression for [S; N] {
+    const CLASS: SqlTypeClass = S::CLASS;
+    const NULLABLE: bool = S::NULLABLE;
+    const ARITY: Option<usize> = match S::ARITY {
+        Some(arity) => Some(arity * N),
+        None => None,
+    };
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
     #[inline(always)]
     fn write_sql_expression(&self, sql: &mut Sql) {
         (self as &[S]).write_sql_expression(sql)
     }
+
+    fn arity(&self) -> Option<usize> {
+        (self as &[S]).arity()
+    }
 }
 
 impl<S: ~const SqlExpression> const SqlExpression for &[S] {
+    const CLASS: SqlTypeClass = S::CLASS;
+    const NULLABLE: bool = S::NULLABLE;
+    const ARITY: Option<usize> = None;
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
     fn write_sql_expression(&self, sql: &mut Sql) {
         let mut projs = *self;
 
         while let Some((cur, rest)) = projs.split_first() {
             cur.write_sql_expression(sql);
 
-            if rest.is_empty() {
+            if !rest.is_empty() {
                 sql.comma();
             }
 
             projs = rest;
         }
     }
+
+    fn arity(&self) -> Option<usize> {
+        let mut arity = Some(0);
+        let mut projs = *self;
+
+        while let Some((cur, rest)) = projs.split_first() {
+            arity = add_arities(arity, cur.arity());
+            projs = rest;
+        }
+
+        arity
+    }
+}
+
+pub(crate) const fn add_arities(lhs: Option<usize>, rhs: Option<usize>) -> Option<usize> {
+    match (lhs, rhs) {
+        (Some(lhs), Some(rhs)) => Some(lhs + rhs),
+        _ => None,
+    }
+}
+
+/// The class of a list of a single column, unknown for several.
+pub(crate) const fn single_class(classes: &[SqlTypeClass]) -> SqlTypeClass {
+    match classes {
+        [class] => *class,
+        _ => SqlTypeClass::Unknown,
+    }
 }
 
 macro_rules! impl_sql_expression_tuples {
@@ -51,18 +177,42 @@ macro_rules! impl_sql_expression_tuples {
         where
             $($x: ~const SqlExpression,)+
         {
+            const CLASS: SqlTypeClass = {
+                let class = SqlTypeClass::Unknown;
+                $(let class = class.unify($x::CLASS);)+
+                class
+            };
+            const NULLABLE: bool = $($x::NULLABLE)||+;
+            const ARITY: Option<usize> = {
+                let arity = Some(0);
+                $(let arity = add_arities(arity, $x::ARITY);)+
+                arity
+            };
+            const CLASSES: &'static [SqlTypeClass] = &[$(single_class($x::CLASSES)),+];
+
             fn write_sql_expression(&self, sql: &mut Sql) {
                 let total = ${count(x)};
 
                 $(
                     let val: &$x = &self.${index()};
                     val.write_sql_expression(sql);
-                    
+
                     if ${index()} != total - 1 {
                         sql.comma();
                     }
                 )+
             }
+
+            fn arity(&self) -> Option<usize> {
+                let mut arity = Some(0);
+
+                $(
+                    let val: &$x = &self.${index()};
+                    arity = add_arities(arity, val.arity());
+                )+
+
+                arity
+            }
         }
     };
 }
@@ -80,21 +230,185 @@ impl_sql_expression_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10,));
 impl_sql_expression_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11,));
 impl_sql_expression_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,));
 
+/// Operator precedence levels, from the loosest to the tightest binding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Precedence {
+    Or,
+    And,
+    Not,
+    Is,
+    Comparison,
+    Predicate,
+    Additive,
+    Multiplicative,
+    Atom,
+}
+
+impl Precedence {
+    pub const fn binds_tighter_than(self, other: Precedence) -> bool {
+        self as u8 > other as u8
+    }
+}
+
+/// Panics unless values of both classes can be compared, for use in bounds as
+/// `[(); check_comparable(L::CLASS, R::CLASS)]:`, which makes mismatches
+/// compile errors.
+#[doc(hidden)]
+pub const fn check_comparable(lhs: SqlTypeClass, rhs: SqlTypeClass) -> usize {
+    if !lhs.is_compatible_with(rhs) {
+        panic!("can't compare values of incompatible SQL types");
+    }
+
+    0
+}
+
+/// Like [`check_comparable`], for the number of columns of the operands of a
+/// set operation, unless unknown.
+#[doc(hidden)]
+pub const fn check_arity(lhs: Option<usize>, rhs: Option<usize>) -> usize {
+    if let (Some(lhs), Some(rhs)) = (lhs, rhs) {
+        if lhs != rhs {
+            panic!("set operation operands must return the same number of columns");
+        }
+    }
+
+    0
+}
+
+/// Like [`check_comparable`], for assigning a value of class `value` to a
+/// column of class `column`.
+#[doc(hidden)]
+pub const fn check_assignable(column: SqlTypeClass, value: SqlTypeClass) -> usize {
+    if !column.is_compatible_with(value) {
+        panic!("can't assign a value of an incompatible SQL type");
+    }
+
+    0
+}
+
+/// Like [`check_assignable`], for assigning a value that may be `NULL` to a
+/// column that may be `NOT NULL`.
+#[doc(hidden)]
+pub const fn check_nullable(column: bool, value: bool) -> usize {
+    if !column && value {
+        panic!("can't assign a nullable value to a NOT NULL column");
+    }
+
+    0
+}
+
+/// Like [`check_assignable`], for a row of values filling a list of columns, as
+/// in `INSERT`, which must have the same number of columns unless unknown.
+#[doc(hidden)]
+pub const fn check_row(
+    columns: Option<usize>,
+    column_classes: &[SqlTypeClass],
+    values: Option<usize>,
+    value_classes: &[SqlTypeClass],
+) -> usize {
+    if let (Some(columns), Some(values)) = (columns, values) {
+        if columns != values {
+            panic!("the number of values doesn't match the number of columns");
+        }
+    }
+
+    if column_classes.len() == value_classes.len() {
+        let mut idx = 0;
+        while idx < column_classes.len() {
+            check_assignable(column_classes[idx], value_classes[idx]);
+            idx += 1;
+        }
+    }
+
+    0
+}
+
+/// Like [`check_arity`], for the constrained and referenced columns of a
+/// foreign key.
+#[doc(hidden)]
+pub const fn check_foreign_key(columns: Option<usize>, referenced: Option<usize>) -> usize {
+    if let (Some(columns), Some(referenced)) = (columns, referenced) {
+        if columns != referenced {
+            panic!("a foreign key must reference as many columns as it constrains");
+        }
+    }
+
+    0
+}
+
+/// Like [`check_comparable`], for the operands of arithmetic operators.
+#[doc(hidden)]
+pub const fn check_numeric(lhs: SqlTypeClass, rhs: SqlTypeClass) -> usize {
+    if !SqlTypeClass::Numeric.is_compatible_with(lhs)
+        || !SqlTypeClass::Numeric.is_compatible_with(rhs)
+    {
+        panic!("arithmetic operands must be numeric");
+    }
+
+    0
+}
+
+pub(crate) const fn str_eq(lhs: &str, rhs: &str) -> bool {
+    let (lhs, rhs) = (lhs.as_bytes(), rhs.as_bytes());
+
+    if lhs.len() != rhs.len() {
+        return false;
+    }
+
+    let mut idx = 0;
+    while idx < lhs.len() {
+        if lhs[idx] != rhs[idx] {
+            return false;
+        }
+
+        idx += 1;
+    }
+
+    true
+}
+
 pub struct Sql {
     query: ConstString,
-    bindings: u8,
+    bindings: u32,
+    /// The byte offsets of the numbers of the numbered placeholders, as in
+    /// `$1` or `@p1`, renumbered by [`Sql::push_sql`].
+    placeholders: ConstVec<usize>,
+    dialect: Dialect,
+    quoting: Quoting,
 }
 
 impl const Default for Sql {
     fn default() -> Self {
+        Self::new(Dialect::default())
+    }
+}
+
+impl Sql {
+    pub const fn new(dialect: Dialect) -> Self {
         Self {
             query: ConstString::default(),
             bindings: 0,
+            placeholders: ConstVec::default(),
+            dialect,
+            quoting: Quoting::default(),
         }
     }
-}
 
-impl Sql {
+    /// Sets when identifiers are quoted, only quoting the ones that need it by
+    /// default.
+    pub const fn with_quoting(mut self, quoting: Quoting) -> Self {
+        self.quoting = quoting;
+        self
+    }
+
+    pub const fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    pub const fn quoting(&self) -> Quoting {
+        self.quoting
+    }
+
     pub const fn push_str(&mut self, part: &str) -> &mut Sql {
         self.query.push_str(part);
         self
@@ -105,11 +419,77 @@ impl Sql {
         self
     }
 
+    /// Writes an identifier, quoted as required by the quoting policy with
+    /// any embedded closing quote doubled.
+    pub const fn push_ident(&mut self, ident: &str) -> &mut Sql {
+        if !self.quoting.needs_quotes(ident, self.dialect) {
+            return self.push_str(ident);
+        }
+
+        let (open, close) = self.dialect.identifier_quotes();
+        let bytes = ident.as_bytes();
+
+        self.push(open);
+
+        let mut idx = 0;
+        while idx < bytes.len() {
+            if bytes[idx] == close {
+                self.push(close);
+            }
+
+            self.push(bytes[idx]);
+            idx += 1;
+        }
+
+        self.push(close)
+    }
+
     pub const fn push_u64(&mut self, num: u64) -> &mut Sql {
         crate::fmt::fmt_u64(&mut self.query, num);
         self
     }
 
+    pub const fn push_i64(&mut self, num: i64) -> &mut Sql {
+        crate::fmt::fmt_i64(&mut self.query, num);
+        self
+    }
+
+    pub const fn push_u128(&mut self, num: u128) -> &mut Sql {
+        crate::fmt::fmt_u128(&mut self.query, num);
+        self
+    }
+
+    pub const fn push_i128(&mut self, num: i128) -> &mut Sql {
+        crate::fmt::fmt_i128(&mut self.query, num);
+        self
+    }
+
+    /// Writes `mantissa / 10^scale`, with exactly `scale` fractional digits.
+    pub const fn push_decimal(&mut self, mantissa: i128, scale: u32) -> &mut Sql {
+        crate::fmt::fmt_decimal(&mut self.query, mantissa, scale);
+        self
+    }
+
+    /// Writes `num` in lowercase hexadecimal, zero padded to at least
+    /// `min_width` digits.
+    pub const fn push_hex(&mut self, num: u128, min_width: usize) -> &mut Sql {
+        crate::fmt::fmt_hex(&mut self.query, num, min_width);
+        self
+    }
+
+    /// Writes the shortest representation of `num` that reads back as the
+    /// same value, panicking for NaN and infinities.
+    pub const fn push_f64(&mut self, num: f64) -> &mut Sql {
+        crate::fmt::fmt_f64(&mut self.query, num);
+        self
+    }
+
+    /// Like [`Sql::push_f64`], for an `f32`.
+    pub const fn push_f32(&mut self, num: f32) -> &mut Sql {
+        crate::fmt::fmt_f32(&mut self.query, num);
+        self
+    }
+
     pub const fn spacing(&mut self) -> &mut Sql {
         self.push(b' ')
     }
@@ -122,18 +502,97 @@ impl Sql {
         self.push(b'.')
     }
 
+    /// Writes an operand of an operator, parenthesizing it unless it binds
+    /// tighter than `precedence`.
+    pub const fn push_operand<E>(&mut self, operand: &E, precedence: Precedence) -> &mut Sql
+    where
+        E: ~const SqlExpression,
+    {
+        if E::PRECEDENCE.binds_tighter_than(precedence) {
+            operand.write_sql_expression(self);
+        } else {
+            self.push(b'(');
+            operand.write_sql_expression(self);
+            self.push(b')');
+        }
+
+        self
+    }
+
+    /// Like [`Sql::push_operand`], for an operand that must be a search
+    /// condition, as those of `AND`, `OR` and `NOT`.
+    pub const fn push_predicate<E>(&mut self, predicate: &E, precedence: Precedence) -> &mut Sql
+    where
+        E: ~const SqlExpression,
+    {
+        if E::PRECEDENCE.binds_tighter_than(precedence) {
+            predicate.write_sql_predicate(self);
+        } else {
+            self.push(b'(');
+            predicate.write_sql_predicate(self);
+            self.push(b')');
+        }
+
+        self
+    }
+
     pub const fn push_binding(&mut self, binding: &str) -> &mut Sql {
         self.push_str(binding);
         self.bindings += 1;
         self
     }
 
+    /// Writes the placeholder of the next positional binding, as expected by
+    /// the dialect.
+    pub const fn push_placeholder(&mut self) -> &mut Sql {
+        self.bindings += 1;
+        let position = self.bindings as u64;
+
+        match self.dialect {
+            Dialect::Postgres => self.push(b'$').push_position(position),
+            Dialect::MySql | Dialect::Sqlite | Dialect::Ansi => self.push(b'?'),
+            Dialect::SqlServer => self.push_str("@p").push_position(position),
+        }
+    }
+
+    /// Writes the number of a numbered placeholder, keeping track of where it
+    /// is.
+    const fn push_position(&mut self, position: u64) -> &mut Sql {
+        self.placeholders.push(self.query.as_str().len());
+        self.push_u64(position)
+    }
+
+    /// Appends a separately rendered query, along with its bindings.
+    ///
+    /// Numbered placeholders in `other` are renumbered to follow the bindings
+    /// already in this query.
     pub const fn push_sql(&mut self, other: &Sql) {
-        self.query.push_str(other.query.as_str());
+        let bytes = other.query.as_str().as_bytes();
+        let placeholders = other.placeholders.as_slice();
+
+        let mut idx = 0;
+        let mut placeholder = 0;
+        while idx < bytes.len() {
+            if placeholder < placeholders.len() && placeholders[placeholder] == idx {
+                let mut position = 0;
+                while idx < bytes.len() && bytes[idx].is_ascii_digit() {
+                    position = position * 10 + (bytes[idx] - b'0') as u64;
+                    idx += 1;
+                }
+
+                self.push_position(position + self.bindings as u64);
+                placeholder += 1;
+                continue;
+            }
+
+            self.push(bytes[idx]);
+            idx += 1;
+        }
+
         self.bindings += other.bindings;
     }
 
-    pub const fn bindings(&self) -> u8 {
+    pub const fn bindings(&self) -> u32 {
         self.bindings
     }
 
@@ -173,4 +632,38 @@ mod tests {
 
         assert_eq!(SQL, "select bla");
     }
+
+    #[test]
+    fn test_push_sql_renumbers_placeholders() {
+        const fn spliced(dialect: Dialect) -> &'static str {
+            let mut sql = Sql::new(dialect);
+            sql.push_placeholder().push_str(" AND ");
+
+            let mut other = Sql::new(dialect);
+            other
+                .push_str("'$1 @p1' = ")
+                .push_placeholder()
+                .push_str(" AND \"$2\" = ")
+                .push_placeholder();
+
+            sql.push_sql(&other);
+            sql.push_str(" AND ").push_placeholder();
+
+            // The renumbered placeholders are renumbered again when nested.
+            let mut outer = Sql::new(dialect);
+            outer.push_placeholder().push_str(" AND ").push_sql(&sql);
+            outer.into_str()
+        }
+
+        static PG: &str = spliced(Dialect::Postgres);
+        static MSSQL: &str = spliced(Dialect::SqlServer);
+        static MYSQL: &str = spliced(Dialect::MySql);
+
+        assert_eq!(PG, "$1 AND $2 AND '$1 @p1' = $3 AND \"$2\" = $4 AND $5");
+        assert_eq!(
+            MSSQL,
+            "@p1 AND @p2 AND '$1 @p1' = @p3 AND \"$2\" = @p4 AND @p5"
+        );
+        assert_eq!(MYSQL, "? AND ? AND '$1 @p1' = ? AND \"$2\" = ? AND ?");
+    }
 }
@@ -0,0 +1,304 @@
+//! Aggregate functions.
+use std::marker::Destruct;
+
+use super::{check_numeric, window::Over, Sql, SqlExpression, Star};
+use crate::{dialect::Dialect, schema::SqlTypeClass};
+
+/// The aggregate functions known to the crate, which determine the type of
+/// an [`Aggregate`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AggregateFunction {
+    /// A function called by name through [`Aggregate::new`], of unknown type.
+    Other,
+    Count,
+    Sum,
+    Avg,
+    Min,
+    Max,
+    ArrayAgg,
+    StringAgg,
+}
+
+/// `NAME([DISTINCT] args) [FILTER (WHERE filter)]`.
+pub struct Aggregate<Args, Filter = (), const F: AggregateFunction = { AggregateFunction::Other }> {
+    name: &'static str,
+    args: Args,
+    distinct: bool,
+    filter: Option<Filter>,
+}
+
+impl<Args> Aggregate<Args> {
+    /// An aggregate function `name` called with `args`.
+    ///
+    /// Multiple arguments are given as a tuple.
+    pub const fn new(name: &'static str, args: Args) -> Self {
+        Self::function(name, args)
+    }
+}
+
+impl<Args, const F: AggregateFunction> Aggregate<Args, (), F> {
+    const fn function(name: &'static str, args: Args) -> Self {
+        Self {
+            name,
+            args,
+            distinct: false,
+            filter: None,
+        }
+    }
+}
+
+impl<Args, Filter, const F: AggregateFunction> Aggregate<Args, Filter, F> {
+    /// Aggregates only the distinct values of the arguments.
+    pub const fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    /// Aggregates only the rows matching `predicate`.
+    pub const fn filter<P>(self, predicate: P) -> Aggregate<Args, P, F>
+    where
+        Self: ~const Destruct,
+    {
+        Aggregate {
+            filter: Some(predicate),
+            ..self
+        }
+    }
+
+    /// Evaluates the aggregate over `window` instead of a group of rows, see
+    /// [`window`](crate::expression::window).
+    pub const fn over<W>(self, window: W) -> Over<Self, W> {
+        Over::new(self, window)
+    }
+}
+
+impl<Args, Filter, const F: AggregateFunction> const SqlExpression for Aggregate<Args, Filter, F>
+where
+    Args: ~const SqlExpression,
+    Filter: ~const SqlExpression,
+{
+    const CLASS: SqlTypeClass = match F {
+        AggregateFunction::Count | AggregateFunction::Sum | AggregateFunction::Avg => {
+            SqlTypeClass::Numeric
+        }
+        AggregateFunction::Min | AggregateFunction::Max => Args::CLASS,
+        AggregateFunction::ArrayAgg => SqlTypeClass::Array,
+        AggregateFunction::StringAgg => SqlTypeClass::Textual,
+        AggregateFunction::Other => SqlTypeClass::Unknown,
+    };
+    /// `COUNT` is never `NULL`, while the other known functions are `NULL` on
+    /// no rows.
+    const NULLABLE: bool = !matches!(F, AggregateFunction::Count | AggregateFunction::Other);
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let name = match (F, sql.dialect()) {
+            (
+                AggregateFunction::ArrayAgg,
+                Dialect::MySql | Dialect::Sqlite | Dialect::SqlServer,
+            ) => {
+                panic!("ARRAY_AGG is not supported by MySQL, SQLite and SQL Server");
+            }
+            (AggregateFunction::StringAgg, Dialect::MySql | Dialect::Sqlite) => "GROUP_CONCAT",
+            (AggregateFunction::StringAgg, Dialect::SqlServer) if self.distinct => {
+                panic!("STRING_AGG(DISTINCT ...) is not supported by SQL Server");
+            }
+            _ => self.name,
+        };
+
+        sql.push_str(name).push(b'(');
+
+        if self.distinct {
+            sql.push_str("DISTINCT ");
+        }
+
+        self.args.write_sql_expression(sql);
+        sql.push(b')');
+
+        if let Some(filter) = &self.filter {
+            if matches!(sql.dialect(), Dialect::MySql | Dialect::SqlServer) {
+                panic!("aggregate FILTER clauses are not supported by MySQL and SQL Server");
+            }
+
+            sql.push_str(" FILTER (WHERE ");
+            filter.write_sql_predicate(sql);
+            sql.push(b')');
+        }
+    }
+}
+
+/// `COUNT(*)`.
+pub const fn count_all() -> Aggregate<Star, (), { AggregateFunction::Count }> {
+    Aggregate::function("COUNT", Star)
+}
+
+pub const fn count<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::Count }> {
+    Aggregate::function("COUNT", expr)
+}
+
+pub const fn sum<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::Sum }>
+where
+    E: SqlExpression,
+    [(); check_numeric(E::CLASS, E::CLASS)]:,
+{
+    Aggregate::function("SUM", expr)
+}
+
+pub const fn avg<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::Avg }>
+where
+    E: SqlExpression,
+    [(); check_numeric(E::CLASS, E::CLASS)]:,
+{
+    Aggregate::function("AVG", expr)
+}
+
+pub const fn min<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::Min }> {
+    Aggregate::function("MIN", expr)
+}
+
+pub const fn max<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::Max }> {
+    Aggregate::function("MAX", expr)
+}
+
+/// `ARRAY_AGG(expr)`. MySQL, SQLite and SQL Server have no arrays, so
+/// rendering it there panics.
+pub const fn array_agg<E>(expr: E) -> Aggregate<E, (), { AggregateFunction::ArrayAgg }> {
+    Aggregate::function("ARRAY_AGG", expr)
+}
+
+/// `STRING_AGG(expr, delimiter)`, the values of `expr` joined by `delimiter`.
+///
+/// MySQL renders it as `GROUP_CONCAT(expr SEPARATOR delimiter)` and SQLite as
+/// `GROUP_CONCAT(expr,delimiter)`. SQL Server doesn't support it with
+/// [`distinct`](Aggregate::distinct).
+pub const fn string_agg<E, D>(
+    expr: E,
+    delimiter: D,
+) -> Aggregate<StringAggArgs<E, D>, (), { AggregateFunction::StringAgg }> {
+    Aggregate::function("STRING_AGG", StringAggArgs { expr, delimiter })
+}
+
+/// The arguments of [`string_agg`], `expr,delimiter` or, for MySQL,
+/// `expr SEPARATOR delimiter`.
+pub struct StringAggArgs<E, D> {
+    expr: E,
+    delimiter: D,
+}
+
+impl<E, D> const SqlExpression for StringAggArgs<E, D>
+where
+    E: ~const SqlExpression,
+    D: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.expr.write_sql_expression(sql);
+
+        if matches!(sql.dialect(), Dialect::MySql) {
+            sql.push_str(" SEPARATOR ");
+        } else {
+            sql.comma();
+        }
+
+        self.delimiter.write_sql_expression(sql);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{expression::ops::SqlExpressionExt, schema::fixtures::*};
+
+    #[test]
+    fn test_aggregates() {
+        static SQL: &str = (
+            count_all(),
+            count(USERS_ID),
+            count(USERS_EMAIL).distinct(),
+            sum(USERS_ID),
+            avg(USERS_ID),
+            min(USERS_NAME),
+            max(USERS_NAME),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "COUNT(*),COUNT(users.id),COUNT(DISTINCT users.email),SUM(users.id),\
+             AVG(users.id),MIN(users.name),MAX(users.name)"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_classes() {
+        assert_eq!(
+            <Aggregate<Star, (), { AggregateFunction::Count }>>::CLASS,
+            SqlTypeClass::Numeric
+        );
+        assert_eq!(
+            <Aggregate<TextColumn, (), { AggregateFunction::Max }>>::CLASS,
+            SqlTypeClass::Textual
+        );
+        assert_eq!(<Aggregate<TextColumn>>::CLASS, SqlTypeClass::Unknown);
+
+        static SQL: &str = USERS_NAME
+            .eq(min(USERS_NAME))
+            .and(USERS_ID.gt(avg(POSTS_ID)))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "users.name = MIN(users.name) AND users.id > AVG(posts.id)"
+        );
+    }
+
+    #[test]
+    fn test_aggregate_filter() {
+        static SQL: &str = (
+            array_agg(POSTS_ID).filter(POSTS_TITLE.is_not_null()),
+            string_agg(POSTS_TITLE, USERS_NAME)
+                .distinct()
+                .filter(POSTS_ID.gt(USERS_ID)),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "ARRAY_AGG(posts.id) FILTER (WHERE posts.title IS NOT NULL),\
+             STRING_AGG(DISTINCT posts.title,users.name) FILTER (WHERE posts.id > users.id)"
+        );
+    }
+
+    #[test]
+    fn test_string_agg_dialects() {
+        static MYSQL: &str = string_agg(POSTS_TITLE, ", ")
+            .distinct()
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+        static SQLITE: &str = string_agg(POSTS_TITLE, ", ")
+            .to_sql_with(Dialect::Sqlite)
+            .into_str();
+        static SQL_SERVER: &str = string_agg(POSTS_TITLE, ", ")
+            .to_sql_with(Dialect::SqlServer)
+            .into_str();
+
+        assert_eq!(MYSQL, "GROUP_CONCAT(DISTINCT posts.title SEPARATOR ', ')");
+        assert_eq!(SQLITE, "GROUP_CONCAT(posts.title,', ')");
+        assert_eq!(SQL_SERVER, "STRING_AGG(posts.title,', ')");
+    }
+
+    #[test]
+    #[should_panic(expected = "ARRAY_AGG is not supported by MySQL, SQLite and SQL Server")]
+    fn test_array_agg_sql_server() {
+        array_agg(POSTS_ID).to_sql_with(Dialect::SqlServer);
+    }
+
+    #[test]
+    #[should_panic(expected = "STRING_AGG(DISTINCT ...) is not supported by SQL Server")]
+    fn test_distinct_string_agg_sql_server() {
+        string_agg(POSTS_TITLE, ", ")
+            .distinct()
+            .to_sql_with(Dialect::SqlServer);
+    }
+}
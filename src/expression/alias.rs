@@ -0,0 +1,28 @@
+//! Expression aliases.
+use super::{Sql, SqlExpression};
+use crate::schema::SqlTypeClass;
+
+/// `expr AS alias`, usually used to name a projection.
+pub struct Aliased<E> {
+    expr: E,
+    alias: &'static str,
+}
+
+impl<E> Aliased<E> {
+    pub const fn new(expr: E, alias: &'static str) -> Self {
+        Self { expr, alias }
+    }
+}
+
+impl<E> const SqlExpression for Aliased<E>
+where
+    E: ~const SqlExpression,
+{
+    const CLASS: SqlTypeClass = E::CLASS;
+    const NULLABLE: bool = E::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.expr.write_sql_expression(sql);
+        sql.push_str(" AS ").push_ident(self.alias);
+    }
+}
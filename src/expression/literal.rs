@@ -0,0 +1,284 @@
+//! Literal values.
+//!
+//! Booleans, numbers and strings are expressions by themselves, as in
+//! `USERS_NAME.eq("bob")`, and are escaped as needed by the dialect. Exact
+//! decimals, byte strings and `NULL` use the [`Decimal`], [`Bytes`] and
+//! [`Null`] wrappers.
+use super::{Sql, SqlExpression};
+use crate::{dialect::Dialect, schema::SqlTypeClass};
+
+/// `TRUE`/`FALSE`, or `1`/`0` for dialects without boolean literals.
+///
+/// SQL Server doesn't accept a bare `1` as a search condition, so it renders
+/// `1 = 1`/`1 = 0` there.
+impl const SqlExpression for bool {
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match (sql.dialect(), *self) {
+            (Dialect::Postgres | Dialect::MySql | Dialect::Ansi, true) => sql.push_str("TRUE"),
+            (Dialect::Postgres | Dialect::MySql | Dialect::Ansi, false) => sql.push_str("FALSE"),
+            (Dialect::Sqlite | Dialect::SqlServer, true) => sql.push(b'1'),
+            (Dialect::Sqlite | Dialect::SqlServer, false) => sql.push(b'0'),
+        };
+    }
+
+    fn write_sql_predicate(&self, sql: &mut Sql) {
+        match (sql.dialect(), *self) {
+            (Dialect::SqlServer, true) => {
+                sql.push_str("1 = 1");
+            }
+            (Dialect::SqlServer, false) => {
+                sql.push_str("1 = 0");
+            }
+            _ => self.write_sql_expression(sql),
+        }
+    }
+}
+
+macro_rules! impl_integer_literals {
+    ($push: ident, $as: ty, $($ty: ty),+) => {
+        $(
+            impl const SqlExpression for $ty {
+                const CLASS: SqlTypeClass = SqlTypeClass::Numeric;
+
+                fn write_sql_expression(&self, sql: &mut Sql) {
+                    sql.$push(*self as $as);
+                }
+            }
+        )+
+    };
+}
+
+impl_integer_literals!(push_u64, u64, u8, u16, u32, u64);
+impl_integer_literals!(push_i64, i64, i8, i16, i32, i64);
+impl_integer_literals!(push_u128, u128, u128);
+impl_integer_literals!(push_i128, i128, i128);
+
+impl const SqlExpression for f32 {
+    const CLASS: SqlTypeClass = SqlTypeClass::Numeric;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_f32(*self);
+    }
+}
+
+impl const SqlExpression for f64 {
+    const CLASS: SqlTypeClass = SqlTypeClass::Numeric;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_f64(*self);
+    }
+}
+
+/// A quoted string, with quotes doubled.
+///
+/// MySQL also treats backslashes as escapes, so they are doubled too. Strings
+/// with control characters use Postgres escape strings, `E'...'`, and strings
+/// with non ASCII characters the SQL Server unicode strings, `N'...'`.
+///
+/// Strings can't contain NUL characters, which no dialect accepts.
+impl const SqlExpression for &str {
+    const CLASS: SqlTypeClass = SqlTypeClass::Textual;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let bytes = self.as_bytes();
+        let dialect = sql.dialect();
+
+        let mut has_control = false;
+        let mut has_non_ascii = false;
+        let mut idx = 0;
+        while idx < bytes.len() {
+            match bytes[idx] {
+                0 => panic!("string literals can't contain NUL characters"),
+                1..=0x1f | 0x7f => has_control = true,
+                0x80.. => has_non_ascii = true,
+                _ => {}
+            }
+            idx += 1;
+        }
+
+        let escape_string = has_control && matches!(dialect, Dialect::Postgres);
+        if escape_string {
+            sql.push(b'E');
+        } else if has_non_ascii && matches!(dialect, Dialect::SqlServer) {
+            sql.push(b'N');
+        }
+
+        sql.push(b'\'');
+
+        let mut idx = 0;
+        while idx < bytes.len() {
+            match bytes[idx] {
+                b'\'' => {
+                    sql.push_str("''");
+                }
+                b'\\' if escape_string || matches!(dialect, Dialect::MySql) => {
+                    sql.push_str("\\\\");
+                }
+                b'\n' if escape_string => {
+                    sql.push_str("\\n");
+                }
+                b'\r' if escape_string => {
+                    sql.push_str("\\r");
+                }
+                b'\t' if escape_string => {
+                    sql.push_str("\\t");
+                }
+                ch @ (1..=0x1f | 0x7f) if escape_string => {
+                    sql.push_str("\\x").push_hex(ch as u128, 2);
+                }
+                ch => {
+                    sql.push(ch);
+                }
+            }
+            idx += 1;
+        }
+
+        sql.push(b'\'');
+    }
+}
+
+/// An exact decimal number, `mantissa / 10^scale`, as in `12.50` for a
+/// mantissa of 1250 and a scale of 2.
+///
+/// Unlike floats, it keeps the given number of fractional digits, as
+/// expected by `NUMERIC`/`DECIMAL` columns.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decimal {
+    pub mantissa: i128,
+    pub scale: u32,
+}
+
+impl Decimal {
+    pub const fn new(mantissa: i128, scale: u32) -> Self {
+        Self { mantissa, scale }
+    }
+}
+
+impl const SqlExpression for Decimal {
+    const CLASS: SqlTypeClass = SqlTypeClass::Numeric;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_decimal(self.mantissa, self.scale);
+    }
+}
+
+/// `NULL`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Null;
+
+impl const SqlExpression for Null {
+    const NULLABLE: bool = true;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("NULL");
+    }
+}
+
+/// A byte string, as in `X'0a1b'`, `'\x0a1b'` for Postgres or `0x0a1b` for
+/// SQL Server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bytes(pub &'static [u8]);
+
+impl const SqlExpression for Bytes {
+    const CLASS: SqlTypeClass = SqlTypeClass::Binary;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match sql.dialect() {
+            Dialect::Postgres => sql.push_str("'\\x"),
+            Dialect::MySql | Dialect::Sqlite | Dialect::Ansi => sql.push_str("X'"),
+            Dialect::SqlServer => sql.push_str("0x"),
+        };
+
+        let mut idx = 0;
+        while idx < self.0.len() {
+            sql.push_hex(self.0[idx] as u128, 2);
+            idx += 1;
+        }
+
+        if !matches!(sql.dialect(), Dialect::SqlServer) {
+            sql.push(b'\'');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{expression::ops::SqlExpressionExt, query::select::from, schema::fixtures::*};
+
+    #[test]
+    fn test_literals() {
+        static SQL: &str = (
+            true,
+            false,
+            0u8,
+            42,
+            -7i64,
+            i64::MIN,
+            u128::MAX,
+            2.5,
+            -0.1f32,
+            1e300,
+            Decimal::new(-1250, 3),
+            Null,
+        )
+            .to_sql()
+            .into_str();
+        static SQLITE: &str = (true, false).to_sql_with(Dialect::Sqlite).into_str();
+
+        assert_eq!(
+            SQL,
+            "TRUE,FALSE,0,42,-7,-9223372036854775808,\
+             340282366920938463463374607431768211455,2.5,-0.1,1E300,-1.250,NULL"
+        );
+        assert_eq!(SQLITE, "1,0");
+    }
+
+    #[test]
+    fn test_sql_server_boolean_predicates() {
+        static SQL: &str = from(USERS)
+            .inner_join(POSTS)
+            .on(true)
+            .select(USERS_ID)
+            .filter(true)
+            .filter(USERS_ID.eq(1).or(false).and(true.not()))
+            .to_sql_with(Dialect::SqlServer)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id FROM [public].users INNER JOIN [public].posts ON 1 = 1 \
+             WHERE 1 = 1 AND (users.id = 1 OR 1 = 0) AND NOT 1 = 1"
+        );
+    }
+
+    #[test]
+    fn test_string_literals() {
+        static SQL: &str = ("it's", "C:\\dir", "a\nb\tc").to_sql().into_str();
+        static MYSQL: &str = ("it's", "C:\\dir", "a\nb")
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+        static SQL_SERVER: &str = ("it's", "añejo").to_sql_with(Dialect::SqlServer).into_str();
+
+        assert_eq!(SQL, "'it''s','C:\\dir',E'a\\nb\\tc'");
+        assert_eq!(MYSQL, "'it''s','C:\\\\dir','a\nb'");
+        assert_eq!(SQL_SERVER, "'it''s',N'añejo'");
+    }
+
+    #[test]
+    fn test_bytes_literals() {
+        static BYTES: Bytes = Bytes(&[0x0a, 0xff, 0x00]);
+
+        assert_eq!(BYTES.to_sql().into_str(), "'\\x0aff00'");
+        assert_eq!(BYTES.to_sql_with(Dialect::MySql).into_str(), "X'0aff00'");
+        assert_eq!(BYTES.to_sql_with(Dialect::SqlServer).into_str(), "0x0aff00");
+    }
+
+    #[test]
+    #[should_panic(expected = "string literals can't contain NUL characters")]
+    fn test_string_literal_with_nul() {
+        "a\0b".to_sql();
+    }
+}
@@ -0,0 +1,782 @@
+//! Comparison, arithmetic and boolean operators.
+//!
+//! The operator nodes are usually built through [`SqlExpressionExt`], which is
+//! implemented for every [`SqlExpression`], e.g.
+//! `USERS_ID.eq(POSTS_USER_ID).and(POSTS_TITLE.is_not_null())`.
+use super::{
+    alias::Aliased,
+    check_comparable, check_numeric,
+    order::{Direction, Ordering},
+    Precedence, Sql, SqlExpression,
+};
+use crate::{dialect::Dialect, schema::SqlTypeClass};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+}
+
+impl ComparisonOp {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ComparisonOp::Eq => " = ",
+            ComparisonOp::Ne => " <> ",
+            ComparisonOp::Lt => " < ",
+            ComparisonOp::Le => " <= ",
+            ComparisonOp::Gt => " > ",
+            ComparisonOp::Ge => " >= ",
+        }
+    }
+}
+
+/// `lhs <op> rhs`.
+pub struct Comparison<L, R> {
+    lhs: L,
+    op: ComparisonOp,
+    rhs: R,
+}
+
+impl<L, R> const SqlExpression for Comparison<L, R>
+where
+    L: ~const SqlExpression,
+    R: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Comparison;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = L::NULLABLE || R::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_operand(&self.lhs, Precedence::Comparison)
+            .push_str(self.op.as_str())
+            .push_operand(&self.rhs, Precedence::Comparison);
+    }
+}
+
+/// `lhs AND rhs`.
+pub struct And<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+impl<L, R> const SqlExpression for And<L, R>
+where
+    L: ~const SqlExpression,
+    R: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::And;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = L::NULLABLE || R::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_predicate(&self.lhs, Precedence::Or)
+            .push_str(" AND ")
+            .push_predicate(&self.rhs, Precedence::Or);
+    }
+}
+
+/// `lhs OR rhs`.
+pub struct Or<L, R> {
+    lhs: L,
+    rhs: R,
+}
+
+impl<L, R> const SqlExpression for Or<L, R>
+where
+    L: ~const SqlExpression,
+    R: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Or;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = L::NULLABLE || R::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.lhs.write_sql_predicate(sql);
+        sql.push_str(" OR ");
+        self.rhs.write_sql_predicate(sql);
+    }
+}
+
+/// `NOT expr`.
+pub struct Not<E> {
+    expr: E,
+}
+
+impl<E> const SqlExpression for Not<E>
+where
+    E: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Not;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = E::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("NOT ")
+            .push_predicate(&self.expr, Precedence::And);
+    }
+}
+
+/// `expr IS [NOT] NULL`.
+pub struct IsNull<E> {
+    expr: E,
+    negated: bool,
+}
+
+impl<E> const SqlExpression for IsNull<E>
+where
+    E: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Is;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_operand(&self.expr, Precedence::Is);
+
+        if self.negated {
+            sql.push_str(" IS NOT NULL");
+        } else {
+            sql.push_str(" IS NULL");
+        }
+    }
+}
+
+/// `expr [NOT] BETWEEN low AND high`.
+pub struct Between<E, Lo, Hi> {
+    expr: E,
+    low: Lo,
+    high: Hi,
+    negated: bool,
+}
+
+impl<E, Lo, Hi> const SqlExpression for Between<E, Lo, Hi>
+where
+    E: ~const SqlExpression,
+    Lo: ~const SqlExpression,
+    Hi: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Predicate;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = E::NULLABLE || Lo::NULLABLE || Hi::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_operand(&self.expr, Precedence::Predicate);
+
+        if self.negated {
+            sql.push_str(" NOT");
+        }
+
+        sql.push_str(" BETWEEN ")
+            .push_operand(&self.low, Precedence::Predicate)
+            .push_str(" AND ")
+            .push_operand(&self.high, Precedence::Predicate);
+    }
+}
+
+/// `expr [NOT] LIKE pattern`, or `ILIKE` for case insensitive matches.
+///
+/// Dialects other than Postgres match case insensitively with
+/// `LOWER(expr) LIKE LOWER(pattern)`.
+pub struct Like<E, P> {
+    expr: E,
+    pattern: P,
+    case_insensitive: bool,
+    negated: bool,
+}
+
+impl<E, P> const SqlExpression for Like<E, P>
+where
+    E: ~const SqlExpression,
+    P: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Predicate;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = E::NULLABLE || P::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let lowercase = self.case_insensitive && !matches!(sql.dialect(), Dialect::Postgres);
+
+        if lowercase {
+            sql.push_str("LOWER(");
+            self.expr.write_sql_expression(sql);
+            sql.push(b')');
+        } else {
+            sql.push_operand(&self.expr, Precedence::Predicate);
+        }
+
+        if self.negated {
+            sql.push_str(" NOT");
+        }
+
+        if lowercase {
+            sql.push_str(" LIKE LOWER(");
+            self.pattern.write_sql_expression(sql);
+            sql.push(b')');
+        } else {
+            if self.case_insensitive {
+                sql.push_str(" ILIKE ");
+            } else {
+                sql.push_str(" LIKE ");
+            }
+
+            sql.push_operand(&self.pattern, Precedence::Predicate);
+        }
+    }
+}
+
+/// `expr [NOT] IN (list)`.
+///
+/// `list` is usually a tuple or a slice of expressions.
+pub struct In<E, L> {
+    expr: E,
+    list: L,
+    negated: bool,
+}
+
+impl<E, L> const SqlExpression for In<E, L>
+where
+    E: ~const SqlExpression,
+    L: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Predicate;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+    const NULLABLE: bool = E::NULLABLE || L::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_operand(&self.expr, Precedence::Predicate);
+
+        if self.negated {
+            sql.push_str(" NOT");
+        }
+
+        sql.push_str(" IN (");
+        self.list.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// Arithmetic operators, by precedence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl ArithmeticOp {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            ArithmeticOp::Add => " + ",
+            ArithmeticOp::Sub => " - ",
+            ArithmeticOp::Mul => " * ",
+            ArithmeticOp::Div => " / ",
+            ArithmeticOp::Rem => " % ",
+        }
+    }
+}
+
+/// `lhs <op> rhs`, on numbers.
+pub struct Arithmetic<L, R, const OP: ArithmeticOp> {
+    lhs: L,
+    rhs: R,
+}
+
+impl<L, R, const OP: ArithmeticOp> const SqlExpression for Arithmetic<L, R, OP>
+where
+    L: ~const SqlExpression,
+    R: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = match OP {
+        ArithmeticOp::Add | ArithmeticOp::Sub => Precedence::Additive,
+        ArithmeticOp::Mul | ArithmeticOp::Div | ArithmeticOp::Rem => Precedence::Multiplicative,
+    };
+    const CLASS: SqlTypeClass = SqlTypeClass::Numeric;
+    const NULLABLE: bool = L::NULLABLE || R::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        // Left associative, so only the right operand needs parentheses at the
+        // same level, as in `a - (b - c)`.
+        let lhs_level = match Self::PRECEDENCE {
+            Precedence::Additive => Precedence::Predicate,
+            _ => Precedence::Additive,
+        };
+
+        sql.push_operand(&self.lhs, lhs_level)
+            .push_str(OP.as_str())
+            .push_operand(&self.rhs, Self::PRECEDENCE);
+    }
+}
+
+/// `[NOT] EXISTS (query)`.
+pub struct Exists<Q> {
+    query: Q,
+    negated: bool,
+}
+
+pub const fn exists<Q>(query: Q) -> Exists<Q> {
+    Exists {
+        query,
+        negated: false,
+    }
+}
+
+pub const fn not_exists<Q>(query: Q) -> Exists<Q> {
+    Exists {
+        query,
+        negated: true,
+    }
+}
+
+impl<Q> const SqlExpression for Exists<Q>
+where
+    Q: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::Not;
+    const CLASS: SqlTypeClass = SqlTypeClass::Boolean;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if self.negated {
+            sql.push_str("NOT ");
+        }
+
+        sql.push_str("EXISTS (");
+        self.query.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// `ANY (query)` or `ALL (query)`, the right hand side of a comparison against
+/// every row returned by a subquery, as in `expr = ANY (SELECT ...)`.
+pub struct Quantified<Q> {
+    query: Q,
+    all: bool,
+}
+
+pub const fn any<Q>(query: Q) -> Quantified<Q> {
+    Quantified { query, all: false }
+}
+
+pub const fn all<Q>(query: Q) -> Quantified<Q> {
+    Quantified { query, all: true }
+}
+
+impl<Q> const SqlExpression for Quantified<Q>
+where
+    Q: ~const SqlExpression,
+{
+    const CLASS: SqlTypeClass = Q::CLASS;
+    const NULLABLE: bool = Q::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if self.all {
+            sql.push_str("ALL (");
+        } else {
+            sql.push_str("ANY (");
+        }
+
+        self.query.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// Operator builders available on every [`SqlExpression`].
+///
+/// Comparisons, `BETWEEN`, `IN` and arithmetic require operands of compatible
+/// [`SqlExpression::CLASS`], so `USERS_ID.eq("1")` doesn't compile.
+#[const_trait]
+#[allow(clippy::wrong_self_convention)]
+pub trait SqlExpressionExt: Sized {
+    fn eq<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Eq, rhs)
+    }
+
+    fn ne<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Ne, rhs)
+    }
+
+    fn lt<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Lt, rhs)
+    }
+
+    fn le<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Le, rhs)
+    }
+
+    fn gt<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Gt, rhs)
+    }
+
+    fn ge<R>(self, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        self.compare(ComparisonOp::Ge, rhs)
+    }
+
+    fn compare<R>(self, op: ComparisonOp, rhs: R) -> Comparison<Self, R>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_comparable(Self::CLASS, R::CLASS)]:,
+    {
+        Comparison { lhs: self, op, rhs }
+    }
+
+    fn add<R>(self, rhs: R) -> Arithmetic<Self, R, { ArithmeticOp::Add }>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_numeric(Self::CLASS, R::CLASS)]:,
+    {
+        Arithmetic { lhs: self, rhs }
+    }
+
+    fn sub<R>(self, rhs: R) -> Arithmetic<Self, R, { ArithmeticOp::Sub }>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_numeric(Self::CLASS, R::CLASS)]:,
+    {
+        Arithmetic { lhs: self, rhs }
+    }
+
+    fn mul<R>(self, rhs: R) -> Arithmetic<Self, R, { ArithmeticOp::Mul }>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_numeric(Self::CLASS, R::CLASS)]:,
+    {
+        Arithmetic { lhs: self, rhs }
+    }
+
+    fn div<R>(self, rhs: R) -> Arithmetic<Self, R, { ArithmeticOp::Div }>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_numeric(Self::CLASS, R::CLASS)]:,
+    {
+        Arithmetic { lhs: self, rhs }
+    }
+
+    fn rem<R>(self, rhs: R) -> Arithmetic<Self, R, { ArithmeticOp::Rem }>
+    where
+        Self: SqlExpression,
+        R: SqlExpression,
+        [(); check_numeric(Self::CLASS, R::CLASS)]:,
+    {
+        Arithmetic { lhs: self, rhs }
+    }
+
+    fn and<R>(self, rhs: R) -> And<Self, R> {
+        And { lhs: self, rhs }
+    }
+
+    fn or<R>(self, rhs: R) -> Or<Self, R> {
+        Or { lhs: self, rhs }
+    }
+
+    fn not(self) -> Not<Self> {
+        Not { expr: self }
+    }
+
+    fn is_null(self) -> IsNull<Self> {
+        IsNull {
+            expr: self,
+            negated: false,
+        }
+    }
+
+    fn is_not_null(self) -> IsNull<Self> {
+        IsNull {
+            expr: self,
+            negated: true,
+        }
+    }
+
+    fn between<Lo, Hi>(self, low: Lo, high: Hi) -> Between<Self, Lo, Hi>
+    where
+        Self: SqlExpression,
+        Lo: SqlExpression,
+        Hi: SqlExpression,
+        [(); check_comparable(Self::CLASS, Lo::CLASS)]:,
+        [(); check_comparable(Self::CLASS, Hi::CLASS)]:,
+    {
+        Between {
+            expr: self,
+            low,
+            high,
+            negated: false,
+        }
+    }
+
+    fn not_between<Lo, Hi>(self, low: Lo, high: Hi) -> Between<Self, Lo, Hi>
+    where
+        Self: SqlExpression,
+        Lo: SqlExpression,
+        Hi: SqlExpression,
+        [(); check_comparable(Self::CLASS, Lo::CLASS)]:,
+        [(); check_comparable(Self::CLASS, Hi::CLASS)]:,
+    {
+        Between {
+            expr: self,
+            low,
+            high,
+            negated: true,
+        }
+    }
+
+    fn like<P>(self, pattern: P) -> Like<Self, P> {
+        Like {
+            expr: self,
+            pattern,
+            case_insensitive: false,
+            negated: false,
+        }
+    }
+
+    fn not_like<P>(self, pattern: P) -> Like<Self, P> {
+        Like {
+            expr: self,
+            pattern,
+            case_insensitive: false,
+            negated: true,
+        }
+    }
+
+    fn ilike<P>(self, pattern: P) -> Like<Self, P> {
+        Like {
+            expr: self,
+            pattern,
+            case_insensitive: true,
+            negated: false,
+        }
+    }
+
+    fn not_ilike<P>(self, pattern: P) -> Like<Self, P> {
+        Like {
+            expr: self,
+            pattern,
+            case_insensitive: true,
+            negated: true,
+        }
+    }
+
+    fn as_(self, alias: &'static str) -> Aliased<Self> {
+        Aliased::new(self, alias)
+    }
+
+    fn asc(self) -> Ordering<Self> {
+        Ordering::new(self, Some(Direction::Asc))
+    }
+
+    fn desc(self) -> Ordering<Self> {
+        Ordering::new(self, Some(Direction::Desc))
+    }
+
+    /// `self NULLS FIRST`, in the default direction. Chain it after
+    /// [`asc`](Self::asc) or [`desc`](Self::desc) for an explicit one.
+    fn nulls_first(self) -> Ordering<Self> {
+        Ordering::new(self, None).nulls_first()
+    }
+
+    /// `self NULLS LAST`, in the default direction.
+    fn nulls_last(self) -> Ordering<Self> {
+        Ordering::new(self, None).nulls_last()
+    }
+
+    fn in_<L>(self, list: L) -> In<Self, L>
+    where
+        Self: SqlExpression,
+        L: SqlExpression,
+        [(); check_comparable(Self::CLASS, L::CLASS)]:,
+    {
+        In {
+            expr: self,
+            list,
+            negated: false,
+        }
+    }
+
+    fn not_in<L>(self, list: L) -> In<Self, L>
+    where
+        Self: SqlExpression,
+        L: SqlExpression,
+        [(); check_comparable(Self::CLASS, L::CLASS)]:,
+    {
+        In {
+            expr: self,
+            list,
+            negated: true,
+        }
+    }
+}
+
+impl<E: SqlExpression> const SqlExpressionExt for E {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{aggregate::count_all, literal::Null, param::Param},
+        query::select::from,
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_comparisons() {
+        static SQL: &str = (
+            USERS_ID.eq(POSTS_USER_ID),
+            USERS_ID.ne(POSTS_USER_ID),
+            USERS_ID.lt(POSTS_USER_ID),
+            USERS_ID.le(POSTS_USER_ID),
+            USERS_ID.gt(POSTS_USER_ID),
+            USERS_ID.ge(POSTS_USER_ID),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "users.id = posts.user_id,users.id <> posts.user_id,users.id < posts.user_id,\
+             users.id <= posts.user_id,users.id > posts.user_id,users.id >= posts.user_id"
+        );
+    }
+
+    #[test]
+    fn test_predicates() {
+        static SQL: &str = (
+            USERS_NAME.is_null(),
+            USERS_NAME.is_not_null(),
+            USERS_ID.between(POSTS_ID, POSTS_USER_ID),
+            USERS_NAME.not_like(USERS_EMAIL),
+            USERS_NAME.ilike(USERS_EMAIL),
+            USERS_ID.in_((POSTS_ID, POSTS_USER_ID)),
+            USERS_ID.not_in([POSTS_ID]),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "users.name IS NULL,users.name IS NOT NULL,\
+             users.id BETWEEN posts.id AND posts.user_id,users.name NOT LIKE users.email,\
+             users.name ILIKE users.email,users.id IN (posts.id,posts.user_id),\
+             users.id NOT IN (posts.id)"
+        );
+    }
+
+    #[test]
+    fn test_precedence() {
+        static SQL: &str = USERS_ID
+            .eq(POSTS_USER_ID)
+            .or(USERS_NAME.is_null())
+            .and(USERS_EMAIL.is_not_null().not())
+            .and(USERS_ID.eq(POSTS_ID).and(USERS_ID.eq(POSTS_USER_ID)).not())
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "(users.id = posts.user_id OR users.name IS NULL) AND NOT users.email IS NOT NULL \
+             AND NOT (users.id = posts.id AND users.id = posts.user_id)"
+        );
+    }
+
+    #[test]
+    fn test_nested_comparisons() {
+        static SQL: &str = USERS_ID
+            .eq(POSTS_ID)
+            .eq(USERS_NAME.is_null())
+            .is_null()
+            .to_sql()
+            .into_str();
+
+        assert_eq!(SQL, "(users.id = posts.id) = (users.name IS NULL) IS NULL");
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        static SQL: &str = (
+            USERS_ID.add(1).mul(POSTS_ID.sub(2)),
+            USERS_ID.sub(POSTS_ID.sub(1)).sub(2),
+            USERS_ID.mul(2).add(1).gt(POSTS_ID.rem(3)),
+            USERS_ID.div(2.5).between(0, Param::<i32>::new()),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "(users.id + 1) * (posts.id - 2),users.id - (posts.id - 1) - 2,\
+             users.id * 2 + 1 > posts.id % 3,users.id / 2.5 BETWEEN 0 AND $1"
+        );
+    }
+
+    #[test]
+    fn test_nullability() {
+        const fn nullable<E: SqlExpression>(_: &E) -> bool {
+            E::NULLABLE
+        }
+
+        assert!(!nullable(&USERS_ID.add(POSTS_ID)));
+        assert!(nullable(&USERS_ID.eq(Param::<Option<i64>>::new())));
+        assert!(nullable(&(1, USERS_ID, EMPLOYEES_MANAGER_ID)));
+        assert!(!nullable(&EMPLOYEES_MANAGER_ID.is_null()));
+    }
+
+    #[test]
+    fn test_sql_type_classes() {
+        assert_eq!(<(TextColumn, &str, Null)>::CLASS, SqlTypeClass::Textual);
+        assert_eq!(<(Null, Param<i64>)>::CLASS, SqlTypeClass::Numeric);
+        assert_eq!(<Param<()>>::CLASS, SqlTypeClass::Unknown);
+        assert_eq!(
+            <Comparison<NumericColumn, i64>>::CLASS,
+            SqlTypeClass::Boolean
+        );
+
+        static SQL: &str = USERS_ID
+            .in_(from(USERS).select(POSTS_USER_ID))
+            .and(USERS_NAME.eq(Param::<Option<String>>::new()))
+            .and(USERS_ID.gt(from(USERS).select(count_all()).subquery()))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "users.id IN (SELECT posts.user_id FROM public.users) AND users.name = $1 \
+             AND users.id > (SELECT COUNT(*) FROM public.users)"
+        );
+    }
+}
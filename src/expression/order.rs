@@ -0,0 +1,129 @@
+//! Ordering terms, as used by `ORDER BY`.
+use super::{Sql, SqlExpression};
+use crate::dialect::Dialect;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Asc,
+    Desc,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Nulls {
+    First,
+    Last,
+}
+
+/// `expr [ASC | DESC] [NULLS FIRST | NULLS LAST]`.
+///
+/// Built with [`SqlExpressionExt::asc`](super::ops::SqlExpressionExt::asc),
+/// [`SqlExpressionExt::desc`](super::ops::SqlExpressionExt::desc) and their
+/// `nulls_first`/`nulls_last` counterparts.
+///
+/// MySQL and SQL Server have no `NULLS FIRST`/`NULLS LAST`, so rendering them
+/// there panics.
+pub struct Ordering<E> {
+    expr: E,
+    direction: Option<Direction>,
+    nulls: Option<Nulls>,
+}
+
+impl<E> Ordering<E> {
+    pub const fn new(expr: E, direction: Option<Direction>) -> Self {
+        Self {
+            expr,
+            direction,
+            nulls: None,
+        }
+    }
+
+    pub const fn nulls_first(mut self) -> Self {
+        self.nulls = Some(Nulls::First);
+        self
+    }
+
+    pub const fn nulls_last(mut self) -> Self {
+        self.nulls = Some(Nulls::Last);
+        self
+    }
+}
+
+impl<E> const SqlExpression for Ordering<E>
+where
+    E: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.expr.write_sql_expression(sql);
+
+        match self.direction {
+            Some(Direction::Asc) => sql.push_str(" ASC"),
+            Some(Direction::Desc) => sql.push_str(" DESC"),
+            None => sql,
+        };
+
+        if self.nulls.is_some() && matches!(sql.dialect(), Dialect::MySql | Dialect::SqlServer) {
+            panic!("NULLS FIRST and NULLS LAST are not supported by MySQL and SQL Server");
+        }
+
+        match self.nulls {
+            Some(Nulls::First) => sql.push_str(" NULLS FIRST"),
+            Some(Nulls::Last) => sql.push_str(" NULLS LAST"),
+            None => sql,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{expression::ops::SqlExpressionExt, query::select::from, schema::fixtures::*};
+
+    #[test]
+    fn test_orderings() {
+        static SQL: &str = from(USERS)
+            .select(USERS_ID)
+            .order_by((
+                USERS_NAME,
+                USERS_ID.desc(),
+                USERS_EMAIL.nulls_last(),
+                USERS_EMAIL.asc().nulls_first(),
+                USERS_NAME.desc().nulls_last(),
+            ))
+            .to_sql()
+            .into_str();
+        static MYSQL: &str = from(USERS)
+            .select(USERS_ID)
+            .order_by((USERS_NAME.asc(), USERS_ID.desc()))
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id FROM public.users ORDER BY users.name,users.id DESC,\
+             users.email NULLS LAST,users.email ASC NULLS FIRST,users.name DESC NULLS LAST"
+        );
+        assert_eq!(
+            MYSQL,
+            "SELECT users.id FROM public.users ORDER BY users.name ASC,users.id DESC"
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "NULLS FIRST and NULLS LAST are not supported by MySQL and SQL Server"
+    )]
+    fn test_nulls_mysql() {
+        USERS_EMAIL.nulls_first().to_sql_with(Dialect::MySql);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "NULLS FIRST and NULLS LAST are not supported by MySQL and SQL Server"
+    )]
+    fn test_nulls_sql_server() {
+        USERS_EMAIL
+            .desc()
+            .nulls_last()
+            .to_sql_with(Dialect::SqlServer);
+    }
+}
@@ -0,0 +1,212 @@
+//! Bind parameters.
+use std::marker::PhantomData;
+
+use super::{Sql, SqlExpression};
+use crate::schema::{SqlType, SqlTypeClass};
+
+/// A positional bind parameter holding a value of type `T`.
+///
+/// Renders as the placeholder expected by the dialect, numbered in the order
+/// the parameters appear in the query: `$1` for Postgres, `?` for MySQL and
+/// SQLite, and `@p1` for SQL Server.
+pub struct Param<T> {
+    _ty: PhantomData<T>,
+}
+
+impl<T> Param<T> {
+    pub const fn new() -> Self {
+        Self { _ty: PhantomData }
+    }
+}
+
+impl<T> const Default for Param<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for Param<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Param<T> {}
+
+impl<T> const SqlExpression for Param<T> {
+    const CLASS: SqlTypeClass = match T::SQL_TYPE {
+        Some(ty) => ty.class(),
+        None => SqlTypeClass::Unknown,
+    };
+    const NULLABLE: bool = T::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_placeholder();
+    }
+}
+
+/// The SQL type of the values bound to a [`Param<Self>`](Param).
+///
+/// Every type implements it, of unknown SQL type and not nullable unless
+/// given a SQL type by specializing the implementation, which opts in to the
+/// type checks:
+///
+/// ```ignore
+/// impl ParamType for MyUuid {
+///     const SQL_TYPE: Option<SqlType> = Some(SqlType::Uuid);
+/// }
+/// ```
+pub trait ParamType {
+    /// `None` when unknown.
+    const SQL_TYPE: Option<SqlType>;
+    const NULLABLE: bool;
+}
+
+impl<T> ParamType for T {
+    default const SQL_TYPE: Option<SqlType> = None;
+    default const NULLABLE: bool = false;
+}
+
+macro_rules! impl_param_types {
+    ($($ty: ty => $sql_ty: expr),+ $(,)?) => {
+        $(
+            impl ParamType for $ty {
+                const SQL_TYPE: Option<SqlType> = Some($sql_ty);
+            }
+        )+
+    };
+}
+
+impl_param_types!(
+    bool => SqlType::Boolean,
+    i8 => SqlType::SmallInt,
+    i16 => SqlType::SmallInt,
+    i32 => SqlType::Integer,
+    i64 => SqlType::BigInt,
+    u8 => SqlType::SmallInt,
+    u16 => SqlType::Integer,
+    u32 => SqlType::BigInt,
+    u64 => SqlType::Numeric(20, 0),
+    f32 => SqlType::Real,
+    f64 => SqlType::Double,
+    String => SqlType::Text,
+    &str => SqlType::Text,
+    Vec<u8> => SqlType::Bytea,
+    &[u8] => SqlType::Bytea,
+);
+
+/// `NULL` when `None`.
+impl<T> ParamType for Option<T> {
+    const SQL_TYPE: Option<SqlType> = T::SQL_TYPE;
+    const NULLABLE: bool = true;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::Dialect,
+        expression::ops::SqlExpressionExt,
+        schema::{fixtures::*, SqlTypeClass},
+    };
+
+    #[test]
+    fn test_placeholders() {
+        static PG: &str = USERS_ID
+            .eq(Param::<i64>::new())
+            .and(USERS_NAME.like(Param::<String>::new()))
+            .to_sql_with(Dialect::Postgres)
+            .into_str();
+        static MYSQL: &str = USERS_ID
+            .eq(Param::<i64>::new())
+            .and(USERS_NAME.like(Param::<String>::new()))
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+        static SQLITE: &str = USERS_ID
+            .eq(Param::<i64>::new())
+            .to_sql_with(Dialect::Sqlite)
+            .into_str();
+        static MSSQL: &str = USERS_ID
+            .between(Param::<i64>::new(), Param::<i64>::new())
+            .to_sql_with(Dialect::SqlServer)
+            .into_str();
+
+        assert_eq!(PG, "users.id = $1 AND users.name LIKE $2");
+        assert_eq!(MYSQL, "users.id = ? AND users.name LIKE ?");
+        assert_eq!(SQLITE, "users.id = ?");
+        assert_eq!(MSSQL, "users.id BETWEEN @p1 AND @p2");
+    }
+
+    #[test]
+    fn test_many_bindings() {
+        const BINDINGS: u32 = {
+            let mut sql = Sql::new(Dialect::MySql);
+
+            let mut idx = 0;
+            while idx < 300 {
+                Param::<i64>::new().write_sql_expression(&mut sql);
+                idx += 1;
+            }
+
+            sql.bindings()
+        };
+
+        assert_eq!(BINDINGS, 300);
+    }
+
+    #[test]
+    fn test_numbered_placeholders() {
+        const P: Param<i64> = Param::new();
+
+        static PG: &str = (P, P, P, P, P, P, P, P, P, P, P)
+            .to_sql_with(Dialect::Postgres)
+            .into_str();
+        static MSSQL: &str = (P, P, P, P, P, P, P, P, P, P)
+            .to_sql_with(Dialect::SqlServer)
+            .into_str();
+        static LAST: &str = {
+            let mut sql = Sql::new(Dialect::Postgres);
+
+            let mut idx = 0;
+            while idx < 300 {
+                P.write_sql_expression(&mut sql);
+                sql.comma();
+                idx += 1;
+            }
+
+            P.write_sql_expression(&mut sql);
+            sql.into_str()
+        };
+
+        assert_eq!(PG, "$1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11");
+        assert_eq!(MSSQL, "@p1,@p2,@p3,@p4,@p5,@p6,@p7,@p8,@p9,@p10");
+        assert!(LAST.ends_with(",$299,$300,$301"));
+    }
+
+    #[test]
+    fn test_param_types() {
+        const fn nullable<E: SqlExpression>(_: &E) -> bool {
+            E::NULLABLE
+        }
+
+        struct Uuid;
+        struct Email;
+
+        impl ParamType for Email {
+            const SQL_TYPE: Option<SqlType> = Some(SqlType::Text);
+        }
+
+        static SQL: &str = USERS_ID
+            .eq(Param::<Uuid>::new())
+            .and(USERS_EMAIL.eq(Param::<Option<Email>>::new()))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(SQL, "users.id = $1 AND users.email = $2");
+        assert_eq!(<Param<Uuid>>::CLASS, SqlTypeClass::Unknown);
+        assert_eq!(<Param<Email>>::CLASS, SqlTypeClass::Textual);
+        assert_eq!(<Param<Option<Email>>>::CLASS, SqlTypeClass::Textual);
+        assert!(!nullable(&Param::<Email>::new()));
+        assert!(nullable(&Param::<Option<Email>>::new()));
+    }
+}
@@ -0,0 +1,454 @@
+//! Window functions and the `OVER` clause.
+//!
+//! Aggregates can be used as window functions too, as in
+//! `sum(x).over(window().order_by(y))` for a running sum.
+use std::marker::Destruct;
+
+use super::{Sql, SqlExpression};
+use crate::{dialect::Dialect, schema::SqlTypeClass};
+
+/// `NAME(args)`, a function only valid with an `OVER` clause.
+pub struct WindowFunction<Args> {
+    name: &'static str,
+    args: Args,
+}
+
+impl<Args> WindowFunction<Args> {
+    /// A window function `name` called with `args`.
+    ///
+    /// Multiple arguments are given as a tuple.
+    pub const fn new(name: &'static str, args: Args) -> Self {
+        Self { name, args }
+    }
+
+    /// Evaluates the function over `window`, either a [`Window`] or a
+    /// [`WindowName`].
+    pub const fn over<W>(self, window: W) -> Over<Self, W> {
+        Over::new(self, window)
+    }
+}
+
+impl<Args> const SqlExpression for WindowFunction<Args>
+where
+    Args: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str(self.name).push(b'(');
+        self.args.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+pub const fn row_number() -> WindowFunction<()> {
+    WindowFunction::new("ROW_NUMBER", ())
+}
+
+pub const fn rank() -> WindowFunction<()> {
+    WindowFunction::new("RANK", ())
+}
+
+pub const fn dense_rank() -> WindowFunction<()> {
+    WindowFunction::new("DENSE_RANK", ())
+}
+
+pub const fn percent_rank() -> WindowFunction<()> {
+    WindowFunction::new("PERCENT_RANK", ())
+}
+
+pub const fn cume_dist() -> WindowFunction<()> {
+    WindowFunction::new("CUME_DIST", ())
+}
+
+pub const fn ntile<N>(buckets: N) -> WindowFunction<N> {
+    WindowFunction::new("NTILE", buckets)
+}
+
+/// `expr` evaluated at the previous row of the partition.
+pub const fn lag<E>(expr: E) -> WindowFunction<E> {
+    WindowFunction::new("LAG", expr)
+}
+
+/// `expr` evaluated `offset` rows before the current one.
+pub const fn lag_by<E, O>(expr: E, offset: O) -> WindowFunction<(E, O)> {
+    WindowFunction::new("LAG", (expr, offset))
+}
+
+/// `expr` evaluated at the next row of the partition.
+pub const fn lead<E>(expr: E) -> WindowFunction<E> {
+    WindowFunction::new("LEAD", expr)
+}
+
+/// `expr` evaluated `offset` rows after the current one.
+pub const fn lead_by<E, O>(expr: E, offset: O) -> WindowFunction<(E, O)> {
+    WindowFunction::new("LEAD", (expr, offset))
+}
+
+pub const fn first_value<E>(expr: E) -> WindowFunction<E> {
+    WindowFunction::new("FIRST_VALUE", expr)
+}
+
+pub const fn last_value<E>(expr: E) -> WindowFunction<E> {
+    WindowFunction::new("LAST_VALUE", expr)
+}
+
+/// `function OVER window`.
+pub struct Over<F, W> {
+    function: F,
+    window: W,
+}
+
+impl<F, W> Over<F, W> {
+    pub const fn new(function: F, window: W) -> Self {
+        Self { function, window }
+    }
+}
+
+impl<F, W> const SqlExpression for Over<F, W>
+where
+    F: ~const SqlExpression,
+    W: ~const SqlExpression,
+{
+    const CLASS: SqlTypeClass = F::CLASS;
+    const NULLABLE: bool = F::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.function.write_sql_expression(sql);
+        self.window.write_sql_expression(sql.push_str(" OVER "));
+    }
+}
+
+/// An empty window specification, `()`, spanning the whole result set.
+pub const fn window() -> Window {
+    Window {
+        partition: None,
+        order: None,
+        frame: None,
+    }
+}
+
+/// A window specification, `(PARTITION BY ... ORDER BY ... frame)`.
+pub struct Window<Partition = (), Order = ()> {
+    partition: Option<Partition>,
+    order: Option<Order>,
+    frame: Option<Frame>,
+}
+
+impl<Partition, Order> Window<Partition, Order> {
+    /// Sets the `PARTITION BY` clause, replacing any previous one.
+    ///
+    /// `terms` is a single expression or a tuple of them.
+    pub const fn partition_by<P>(self, terms: P) -> Window<P, Order>
+    where
+        Self: ~const Destruct,
+    {
+        Window {
+            partition: Some(terms),
+            ..self
+        }
+    }
+
+    /// Sets the `ORDER BY` clause, replacing any previous one.
+    pub const fn order_by<O>(self, terms: O) -> Window<Partition, O>
+    where
+        Self: ~const Destruct,
+    {
+        Window {
+            order: Some(terms),
+            ..self
+        }
+    }
+
+    /// Sets the frame to `ROWS BETWEEN start AND end`.
+    pub const fn rows(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(FrameUnits::Rows, start, end)
+    }
+
+    /// Sets the frame to `RANGE BETWEEN start AND end`.
+    ///
+    /// Offset bounds, as in `2 PRECEDING`, are distances between the values
+    /// of the ordering column, so rendering them panics unless the window is
+    /// ordered by exactly one column.
+    pub const fn range(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(FrameUnits::Range, start, end)
+    }
+
+    /// Sets the frame to `GROUPS BETWEEN start AND end`.
+    ///
+    /// MySQL and SQL Server don't support `GROUPS`, so rendering it there
+    /// panics.
+    pub const fn groups(self, start: FrameBound, end: FrameBound) -> Self {
+        self.frame(FrameUnits::Groups, start, end)
+    }
+
+    const fn frame(mut self, units: FrameUnits, start: FrameBound, end: FrameBound) -> Self {
+        self.frame = Some(Frame { units, start, end });
+        self
+    }
+}
+
+impl<Partition, Order> const SqlExpression for Window<Partition, Order>
+where
+    Partition: ~const SqlExpression,
+    Order: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push(b'(');
+
+        if let Some(partition) = &self.partition {
+            partition.write_sql_expression(sql.push_str("PARTITION BY "));
+        }
+
+        if let Some(order) = &self.order {
+            if self.partition.is_some() {
+                sql.spacing();
+            }
+
+            order.write_sql_expression(sql.push_str("ORDER BY "));
+        }
+
+        if let Some(frame) = &self.frame {
+            let ordered_by_one = match &self.order {
+                Some(order) => matches!(order.arity(), Some(1)),
+                None => false,
+            };
+
+            if matches!(frame.units, FrameUnits::Range) && frame.has_offset() && !ordered_by_one {
+                panic!("RANGE frames with offsets need exactly one ORDER BY column");
+            }
+
+            if self.partition.is_some() || self.order.is_some() {
+                sql.spacing();
+            }
+
+            frame.write_sql_expression(sql);
+        }
+
+        sql.push(b')');
+    }
+}
+
+/// Refers to a window defined in the `WINDOW` clause, see
+/// [`Select::window`](crate::query::select::Select::window).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WindowName(pub &'static str);
+
+impl const SqlExpression for WindowName {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_ident(self.0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameUnits {
+    Rows,
+    Range,
+    Groups,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameBound {
+    UnboundedPreceding,
+    Preceding(u64),
+    CurrentRow,
+    Following(u64),
+    UnboundedFollowing,
+}
+
+impl const SqlExpression for FrameBound {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match *self {
+            FrameBound::UnboundedPreceding => {
+                sql.push_str("UNBOUNDED PRECEDING");
+            }
+            FrameBound::Preceding(offset) => {
+                sql.push_u64(offset).push_str(" PRECEDING");
+            }
+            FrameBound::CurrentRow => {
+                sql.push_str("CURRENT ROW");
+            }
+            FrameBound::Following(offset) => {
+                sql.push_u64(offset).push_str(" FOLLOWING");
+            }
+            FrameBound::UnboundedFollowing => {
+                sql.push_str("UNBOUNDED FOLLOWING");
+            }
+        }
+    }
+}
+
+/// `units BETWEEN start AND end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Frame {
+    units: FrameUnits,
+    start: FrameBound,
+    end: FrameBound,
+}
+
+impl Frame {
+    const fn has_offset(&self) -> bool {
+        matches!(
+            self.start,
+            FrameBound::Preceding(_) | FrameBound::Following(_)
+        ) || matches!(
+            self.end,
+            FrameBound::Preceding(_) | FrameBound::Following(_)
+        )
+    }
+}
+
+impl const SqlExpression for Frame {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match self.units {
+            FrameUnits::Rows => sql.push_str("ROWS"),
+            FrameUnits::Range => sql.push_str("RANGE"),
+            FrameUnits::Groups => {
+                if matches!(sql.dialect(), Dialect::MySql | Dialect::SqlServer) {
+                    panic!("GROUPS frames are not supported by MySQL and SQL Server");
+                }
+
+                sql.push_str("GROUPS")
+            }
+        };
+
+        self.start.write_sql_expression(sql.push_str(" BETWEEN "));
+        self.end.write_sql_expression(sql.push_str(" AND "));
+    }
+}
+
+/// The definitions of the `WINDOW` clause, one `name AS (...)` per call to
+/// [`Select::window`](crate::query::select::Select::window).
+pub struct WindowDefinitions<Prev, W> {
+    prev: Option<Prev>,
+    name: &'static str,
+    window: W,
+}
+
+impl<Prev, W> WindowDefinitions<Prev, W> {
+    pub(crate) const fn new(prev: Option<Prev>, name: &'static str, window: W) -> Self {
+        Self { prev, name, window }
+    }
+}
+
+impl<Prev, W> const SqlExpression for WindowDefinitions<Prev, W>
+where
+    Prev: ~const SqlExpression,
+    W: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if let Some(prev) = &self.prev {
+            prev.write_sql_expression(sql);
+            sql.comma();
+        }
+
+        sql.push_ident(self.name).push_str(" AS ");
+        self.window.write_sql_expression(sql);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{
+            aggregate::{count_all, sum},
+            ops::SqlExpressionExt,
+            param::Param,
+        },
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_window_functions() {
+        static SQL: &str = (
+            row_number().over(window()),
+            rank().over(
+                window()
+                    .partition_by(POSTS_USER_ID)
+                    .order_by(POSTS_ID.desc()),
+            ),
+            lag(POSTS_TITLE).over(window().order_by(POSTS_ID)),
+            lead_by(POSTS_TITLE, Param::<i64>::new()).over(WindowName("w")),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "ROW_NUMBER() OVER (),RANK() OVER (PARTITION BY posts.user_id ORDER BY posts.id DESC),\
+             LAG(posts.title) OVER (ORDER BY posts.id),LEAD(posts.title,$1) OVER w"
+        );
+    }
+
+    #[test]
+    fn test_window_frames() {
+        static SQL: &str = (
+            sum(POSTS_ID).over(
+                window()
+                    .partition_by(POSTS_USER_ID)
+                    .order_by(POSTS_ID)
+                    .rows(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+            ),
+            sum(POSTS_ID).filter(POSTS_TITLE.is_not_null()).over(
+                window()
+                    .order_by(POSTS_ID)
+                    .range(FrameBound::Preceding(2), FrameBound::Following(1)),
+            ),
+            count_all().over(
+                window()
+                    .order_by((POSTS_USER_ID, POSTS_ID))
+                    .range(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+            ),
+            first_value(POSTS_ID).over(
+                window()
+                    .order_by(POSTS_ID)
+                    .groups(FrameBound::CurrentRow, FrameBound::UnboundedFollowing),
+            ),
+        )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SUM(posts.id) OVER (PARTITION BY posts.user_id ORDER BY posts.id \
+             ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW),\
+             SUM(posts.id) FILTER (WHERE posts.title IS NOT NULL) OVER \
+             (ORDER BY posts.id RANGE BETWEEN 2 PRECEDING AND 1 FOLLOWING),\
+             COUNT(*) OVER (ORDER BY posts.user_id,posts.id \
+             RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW),\
+             FIRST_VALUE(posts.id) OVER (ORDER BY posts.id \
+             GROUPS BETWEEN CURRENT ROW AND UNBOUNDED FOLLOWING)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "RANGE frames with offsets need exactly one ORDER BY column")]
+    fn test_range_offset_without_order() {
+        sum(POSTS_ID)
+            .over(window().range(FrameBound::Preceding(2), FrameBound::CurrentRow))
+            .to_sql();
+    }
+
+    #[test]
+    #[should_panic(expected = "RANGE frames with offsets need exactly one ORDER BY column")]
+    fn test_range_offset_with_two_orderings() {
+        sum(POSTS_ID)
+            .over(
+                window()
+                    .order_by((POSTS_USER_ID, POSTS_ID))
+                    .range(FrameBound::CurrentRow, FrameBound::Following(1)),
+            )
+            .to_sql();
+    }
+
+    #[test]
+    #[should_panic(expected = "GROUPS frames are not supported by MySQL and SQL Server")]
+    fn test_groups_mysql() {
+        first_value(POSTS_ID)
+            .over(
+                window()
+                    .order_by(POSTS_ID)
+                    .groups(FrameBound::CurrentRow, FrameBound::UnboundedFollowing),
+            )
+            .to_sql_with(Dialect::MySql);
+    }
+}
@@ -1,17 +1,258 @@
+//! Const formatting of numbers into a [`ConstString`].
 use crate::const_string::ConstString;
 
-pub(crate) const fn fmt_u64(buf: &mut ConstString, n: u64) {
-    const ASCII_NUM_BASE: u8 = 48u8;
+mod float;
 
-    let n_digits = n.ilog10() + 1;
+pub(crate) use float::{fmt_f32, fmt_f64};
 
-    buf.reserve(n_digits as usize);
+const ASCII_NUM_BASE: u8 = 48u8;
 
+pub(crate) const fn fmt_u64(buf: &mut ConstString, n: u64) {
+    fmt_u128(buf, n as u128);
+}
+
+pub(crate) const fn fmt_i64(buf: &mut ConstString, n: i64) {
+    fmt_i128(buf, n as i128);
+}
+
+pub(crate) const fn fmt_u128(buf: &mut ConstString, n: u128) {
+    // `u128::MAX` has 39 digits.
+    let mut digits = [0u8; 39];
+    let mut start = digits.len();
     let mut curr = n;
 
-    while curr > 0 {
-        buf.push_ascii(ASCII_NUM_BASE + (curr % 10).to_le_bytes()[0]);
+    loop {
+        start -= 1;
+        digits[start] = ASCII_NUM_BASE + (curr % 10) as u8;
+        curr /= 10;
+
+        if curr == 0 {
+            break;
+        }
+    }
+
+    while start < digits.len() {
+        buf.push_ascii(digits[start]);
+        start += 1;
+    }
+}
+
+pub(crate) const fn fmt_i128(buf: &mut ConstString, n: i128) {
+    if n < 0 {
+        buf.push_ascii(b'-');
+    }
+
+    fmt_u128(buf, n.unsigned_abs());
+}
 
+/// Writes `mantissa / 10^scale` with exactly `scale` fractional digits, as in
+/// `-0.050` for a mantissa of -50 and a scale of 3.
+pub(crate) const fn fmt_decimal(buf: &mut ConstString, mantissa: i128, scale: u32) {
+    if scale == 0 {
+        fmt_i128(buf, mantissa);
+        return;
+    }
+
+    // Scales above 38 leave no integral digits for any mantissa.
+    let magnitude = mantissa.unsigned_abs();
+    let (integral, fractional) = match 10u128.checked_pow(scale) {
+        Some(divisor) => (magnitude / divisor, magnitude % divisor),
+        None => (0, magnitude),
+    };
+
+    if mantissa < 0 {
+        buf.push_ascii(b'-');
+    }
+
+    fmt_u128(buf, integral);
+    buf.push_ascii(b'.');
+
+    let mut width = 1;
+    let mut curr = fractional / 10;
+    while curr > 0 {
+        width += 1;
         curr /= 10;
     }
+
+    let mut padding = scale - width;
+    while padding > 0 {
+        buf.push_ascii(b'0');
+        padding -= 1;
+    }
+
+    fmt_u128(buf, fractional);
+}
+
+/// Writes `n` in lowercase hexadecimal, zero padded to at least `min_width`
+/// digits.
+pub(crate) const fn fmt_hex(buf: &mut ConstString, n: u128, min_width: usize) {
+    const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+    let mut width = 1;
+    while width < 32 && n >> (4 * width) != 0 {
+        width += 1;
+    }
+
+    let mut padding = min_width.saturating_sub(width);
+    while padding > 0 {
+        buf.push_ascii(b'0');
+        padding -= 1;
+    }
+
+    while width > 0 {
+        width -= 1;
+        buf.push_ascii(HEX_DIGITS[((n >> (4 * width)) & 0xf) as usize]);
+    }
+}
+
+/// Helpers shared by the tests of the integer and float formatting.
+#[cfg(test)]
+mod test_utils {
+    use crate::const_string::ConstString;
+
+    /// The output of `write` into an empty string.
+    pub(super) fn render(write: impl FnOnce(&mut ConstString)) -> &'static str {
+        let mut buf = ConstString::default();
+        write(&mut buf);
+        buf.leak()
+    }
+
+    /// A xorshift pseudo random sequence, the same on every run.
+    pub(super) fn xorshift() -> impl Iterator<Item = u64> {
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+
+        std::iter::from_fn(move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            Some(state)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        test_utils::{render, xorshift},
+        *,
+    };
+
+    /// Edge cases around every power of ten, plus pseudo random values.
+    fn sample_u128() -> Vec<u128> {
+        let mut values: Vec<u128> = (0..=1000).collect();
+
+        let mut power = 1u128;
+        while let Some(next) = power.checked_mul(10) {
+            values.extend([power - 1, power, power + 1]);
+            power = next;
+        }
+        values.extend([power - 1, power, power + 1]);
+        values.extend([
+            u64::MAX as u128,
+            u64::MAX as u128 + 1,
+            u128::MAX - 1,
+            u128::MAX,
+        ]);
+
+        let mut random = xorshift();
+        for _ in 0..5_000 {
+            let (high, low) = (random.next().unwrap(), random.next().unwrap());
+            values.extend([low as u128, (high as u128) << 64 | low as u128]);
+        }
+
+        values
+    }
+
+    #[test]
+    fn test_fmt_const() {
+        const SQL: &str = {
+            let mut buf = ConstString::default();
+            fmt_u64(&mut buf, 0);
+            buf.push_ascii(b',');
+            fmt_u64(&mut buf, 10);
+            buf.push_ascii(b',');
+            fmt_i64(&mut buf, -1234);
+            buf.push_ascii(b',');
+            fmt_decimal(&mut buf, -50, 3);
+            buf.push_ascii(b',');
+            fmt_hex(&mut buf, 0xbeef, 8);
+            buf.leak()
+        };
+
+        assert_eq!(SQL, "0,10,-1234,-0.050,0000beef");
+    }
+
+    #[test]
+    fn test_fmt_multi_digit() {
+        const SQL: &str = {
+            let mut buf = ConstString::default();
+            fmt_u64(&mut buf, 10);
+            buf.push_ascii(b',');
+            fmt_u64(&mut buf, 255);
+            buf.push_ascii(b',');
+            fmt_u64(&mut buf, 1000);
+            buf.push_ascii(b',');
+            fmt_u64(&mut buf, u64::MAX);
+            buf.leak()
+        };
+
+        assert_eq!(SQL, "10,255,1000,18446744073709551615");
+    }
+
+    #[test]
+    fn test_fmt_integers() {
+        for n in sample_u128() {
+            assert_eq!(render(|buf| fmt_u128(buf, n)), n.to_string());
+
+            let signed = n as i128;
+            assert_eq!(render(|buf| fmt_i128(buf, signed)), signed.to_string());
+            assert_eq!(
+                render(|buf| fmt_i128(buf, signed.wrapping_neg())),
+                signed.wrapping_neg().to_string()
+            );
+
+            let n = n as u64;
+            assert_eq!(render(|buf| fmt_u64(buf, n)), n.to_string());
+
+            let signed = n as i64;
+            assert_eq!(render(|buf| fmt_i64(buf, signed)), signed.to_string());
+            assert_eq!(
+                render(|buf| fmt_i64(buf, signed.wrapping_neg())),
+                signed.wrapping_neg().to_string()
+            );
+        }
+    }
+
+    #[test]
+    fn test_fmt_decimal() {
+        for n in sample_u128().into_iter().step_by(7) {
+            for mantissa in [n as i128, (n as i128).wrapping_neg()] {
+                for scale in 0..=40 {
+                    let digits = mantissa.unsigned_abs().to_string();
+                    let digits = format!("{digits:0>width$}", width = scale as usize + 1);
+                    let (integral, fractional) = digits.split_at(digits.len() - scale as usize);
+                    let sign = if mantissa < 0 { "-" } else { "" };
+                    let expected = if scale == 0 {
+                        format!("{sign}{integral}")
+                    } else {
+                        format!("{sign}{integral}.{fractional}")
+                    };
+
+                    assert_eq!(render(|buf| fmt_decimal(buf, mantissa, scale)), expected);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_fmt_hex() {
+        for n in sample_u128().into_iter().step_by(3) {
+            for width in 0..=34 {
+                assert_eq!(
+                    render(|buf| fmt_hex(buf, n, width)),
+                    format!("{n:0width$x}")
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,421 @@
+//! Shortest round-tripping formatting of floats.
+use super::{fmt_u64, ASCII_NUM_BASE};
+use crate::const_string::ConstString;
+
+/// Writes the shortest decimal representation of `value` that parses back to
+/// it, using scientific notation for very large or small magnitudes.
+///
+/// Panics for NaN and infinities, which have no SQL literal.
+pub(crate) const fn fmt_f64(buf: &mut ConstString, value: f64) {
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 52) & 0x7ff) as i32;
+    let fraction = bits & ((1 << 52) - 1);
+
+    fmt_float(
+        buf,
+        bits >> 63 == 1,
+        FloatParts::new(fraction, biased_exponent, 52, 1023, 0x7ff),
+    );
+}
+
+/// Like [`fmt_f64`], with the shortest representation for an `f32`.
+pub(crate) const fn fmt_f32(buf: &mut ConstString, value: f32) {
+    let bits = value.to_bits();
+    let biased_exponent = ((bits >> 23) & 0xff) as i32;
+    let fraction = (bits & ((1 << 23) - 1)) as u64;
+
+    fmt_float(
+        buf,
+        bits >> 31 == 1,
+        FloatParts::new(fraction, biased_exponent, 23, 127, 0xff),
+    );
+}
+
+/// A finite float as `mantissa * 2^exponent`.
+struct FloatParts {
+    mantissa: u64,
+    exponent: i32,
+    /// Whether the next smaller float is closer than the next larger one, as
+    /// happens at powers of two.
+    closer_below: bool,
+}
+
+impl FloatParts {
+    const fn new(
+        fraction: u64,
+        biased_exponent: i32,
+        fraction_bits: u32,
+        bias: i32,
+        max_exponent: i32,
+    ) -> Self {
+        if biased_exponent == max_exponent {
+            panic!("NaN and infinite floats have no SQL literal");
+        }
+
+        if biased_exponent == 0 {
+            Self {
+                mantissa: fraction,
+                exponent: 1 - bias - fraction_bits as i32,
+                closer_below: false,
+            }
+        } else {
+            Self {
+                mantissa: fraction | (1 << fraction_bits),
+                exponent: biased_exponent - bias - fraction_bits as i32,
+                closer_below: fraction == 0 && biased_exponent > 1,
+            }
+        }
+    }
+}
+
+/// Formats with the free-format algorithm from Burger and Dybvig's "Printing
+/// Floating-Point Numbers Quickly and Accurately", which finds the shortest
+/// digits that still round to the same float.
+const fn fmt_float(buf: &mut ConstString, negative: bool, parts: FloatParts) {
+    if negative {
+        buf.push_ascii(b'-');
+    }
+
+    if parts.mantissa == 0 {
+        buf.push_str("0.0");
+        return;
+    }
+
+    // The float is `r / s`, and its neighbours are `m_minus / s` below and
+    // `m_plus / s` above. Everything is doubled so halfway points are integers.
+    let mantissa = BigUint::from_u64(parts.mantissa);
+    let (mut r, mut s, mut m_plus, mut m_minus) = match (parts.exponent >= 0, parts.closer_below) {
+        (true, false) => (
+            mantissa.shl(parts.exponent as u32 + 1),
+            BigUint::from_u64(2),
+            BigUint::from_u64(1).shl(parts.exponent as u32),
+            BigUint::from_u64(1).shl(parts.exponent as u32),
+        ),
+        (true, true) => (
+            mantissa.shl(parts.exponent as u32 + 2),
+            BigUint::from_u64(4),
+            BigUint::from_u64(1).shl(parts.exponent as u32 + 1),
+            BigUint::from_u64(1).shl(parts.exponent as u32),
+        ),
+        (false, false) => (
+            mantissa.shl(1),
+            BigUint::from_u64(1).shl((1 - parts.exponent) as u32),
+            BigUint::from_u64(1),
+            BigUint::from_u64(1),
+        ),
+        (false, true) => (
+            mantissa.shl(2),
+            BigUint::from_u64(1).shl((2 - parts.exponent) as u32),
+            BigUint::from_u64(2),
+            BigUint::from_u64(1),
+        ),
+    };
+
+    // Round-half-even parsing means the bounds are reachable for even
+    // mantissas.
+    let inclusive = parts.mantissa % 2 == 0;
+
+    // Scale so that the value is `0.d1d2... * 10^k`.
+    let mut k = 0i32;
+    while r.add(&m_plus).reaches(&s, inclusive) {
+        s = s.mul_small(10);
+        k += 1;
+    }
+    while !r.add(&m_plus).mul_small(10).reaches(&s, inclusive) {
+        r = r.mul_small(10);
+        m_plus = m_plus.mul_small(10);
+        m_minus = m_minus.mul_small(10);
+        k -= 1;
+    }
+
+    let mut digits = [0u8; 20];
+    let mut len = 0;
+
+    loop {
+        r = r.mul_small(10);
+        m_plus = m_plus.mul_small(10);
+        m_minus = m_minus.mul_small(10);
+
+        let mut digit = 0;
+        while r.cmp(&s) >= 0 {
+            r = r.sub(&s);
+            digit += 1;
+        }
+
+        let low = if inclusive {
+            r.cmp(&m_minus) <= 0
+        } else {
+            r.cmp(&m_minus) < 0
+        };
+        let high = r.add(&m_plus).reaches(&s, inclusive);
+
+        match (low, high) {
+            (false, false) => {
+                digits[len] = digit;
+                len += 1;
+            }
+            (true, false) => {
+                digits[len] = digit;
+                len += 1;
+                break;
+            }
+            (false, true) => {
+                digits[len] = digit + 1;
+                len += 1;
+                break;
+            }
+            (true, true) => {
+                digits[len] = if r.shl(1).cmp(&s) < 0 {
+                    digit
+                } else {
+                    digit + 1
+                };
+                len += 1;
+                break;
+            }
+        }
+    }
+
+    write_float_digits(buf, &digits, len, k);
+}
+
+/// Writes `0.digits * 10^k`, in positional notation when it is short enough.
+const fn write_float_digits(buf: &mut ConstString, digits: &[u8; 20], len: usize, k: i32) {
+    if k > 0 && k <= 17 {
+        let mut idx = 0;
+        while idx < len || idx < k as usize {
+            if idx == k as usize {
+                buf.push_ascii(b'.');
+            }
+
+            buf.push_ascii(ASCII_NUM_BASE + if idx < len { digits[idx] } else { 0 });
+            idx += 1;
+        }
+
+        if len <= k as usize {
+            buf.push_str(".0");
+        }
+    } else if k <= 0 && k > -4 {
+        buf.push_str("0.");
+
+        let mut zeros = -k;
+        while zeros > 0 {
+            buf.push_ascii(b'0');
+            zeros -= 1;
+        }
+
+        let mut idx = 0;
+        while idx < len {
+            buf.push_ascii(ASCII_NUM_BASE + digits[idx]);
+            idx += 1;
+        }
+    } else {
+        buf.push_ascii(ASCII_NUM_BASE + digits[0]);
+
+        if len > 1 {
+            buf.push_ascii(b'.');
+
+            let mut idx = 1;
+            while idx < len {
+                buf.push_ascii(ASCII_NUM_BASE + digits[idx]);
+                idx += 1;
+            }
+        }
+
+        buf.push_ascii(b'E');
+
+        let exponent = k - 1;
+        if exponent < 0 {
+            buf.push_ascii(b'-');
+        }
+
+        fmt_u64(buf, exponent.unsigned_abs() as u64);
+    }
+}
+
+/// Enough 32 bit limbs for the scaled values of any `f64`, which need a bit
+/// over 1100 bits for the smallest subnormals.
+const BIG_UINT_LIMBS: usize = 40;
+
+/// A fixed width unsigned integer, with little endian limbs.
+#[derive(Clone, Copy)]
+struct BigUint {
+    limbs: [u32; BIG_UINT_LIMBS],
+}
+
+impl BigUint {
+    const fn from_u64(n: u64) -> Self {
+        let mut limbs = [0; BIG_UINT_LIMBS];
+        limbs[0] = n as u32;
+        limbs[1] = (n >> 32) as u32;
+        Self { limbs }
+    }
+
+    const fn shl(&self, bits: u32) -> Self {
+        let limb_shift = (bits / 32) as usize;
+        let bit_shift = bits % 32;
+        let mut limbs = [0; BIG_UINT_LIMBS];
+
+        let mut idx = BIG_UINT_LIMBS;
+        while idx > limb_shift {
+            idx -= 1;
+
+            let src = idx - limb_shift;
+            let mut limb = self.limbs[src] << bit_shift;
+            if bit_shift > 0 && src > 0 {
+                limb |= self.limbs[src - 1] >> (32 - bit_shift);
+            }
+
+            limbs[idx] = limb;
+        }
+
+        Self { limbs }
+    }
+
+    const fn add(&self, other: &Self) -> Self {
+        let mut limbs = [0; BIG_UINT_LIMBS];
+        let mut carry = 0u64;
+
+        let mut idx = 0;
+        while idx < BIG_UINT_LIMBS {
+            let sum = self.limbs[idx] as u64 + other.limbs[idx] as u64 + carry;
+            limbs[idx] = sum as u32;
+            carry = sum >> 32;
+            idx += 1;
+        }
+
+        Self { limbs }
+    }
+
+    /// `self - other`, where `other` must not be greater than `self`.
+    const fn sub(&self, other: &Self) -> Self {
+        let mut limbs = [0; BIG_UINT_LIMBS];
+        let mut borrow = 0i64;
+
+        let mut idx = 0;
+        while idx < BIG_UINT_LIMBS {
+            let diff = self.limbs[idx] as i64 - other.limbs[idx] as i64 - borrow;
+            if diff < 0 {
+                limbs[idx] = (diff + (1 << 32)) as u32;
+                borrow = 1;
+            } else {
+                limbs[idx] = diff as u32;
+                borrow = 0;
+            }
+            idx += 1;
+        }
+
+        Self { limbs }
+    }
+
+    const fn mul_small(&self, factor: u32) -> Self {
+        let mut limbs = [0; BIG_UINT_LIMBS];
+        let mut carry = 0u64;
+
+        let mut idx = 0;
+        while idx < BIG_UINT_LIMBS {
+            let product = self.limbs[idx] as u64 * factor as u64 + carry;
+            limbs[idx] = product as u32;
+            carry = product >> 32;
+            idx += 1;
+        }
+
+        Self { limbs }
+    }
+
+    /// -1, 0 or 1 as `self` is less than, equal to or greater than `other`.
+    const fn cmp(&self, other: &Self) -> i8 {
+        let mut idx = BIG_UINT_LIMBS;
+        while idx > 0 {
+            idx -= 1;
+
+            if self.limbs[idx] < other.limbs[idx] {
+                return -1;
+            } else if self.limbs[idx] > other.limbs[idx] {
+                return 1;
+            }
+        }
+
+        0
+    }
+
+    /// `self >= other` when `inclusive`, `self > other` otherwise.
+    const fn reaches(&self, other: &Self, inclusive: bool) -> bool {
+        let cmp = self.cmp(other);
+        cmp > 0 || (inclusive && cmp == 0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::fmt::test_utils::{render, xorshift};
+
+    /// The significant digits of Rust's own shortest representation.
+    fn std_digits(formatted: &str) -> String {
+        let mantissa = formatted.split(['e', 'E']).next().unwrap();
+        let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+        digits.trim_matches('0').to_owned()
+    }
+
+    #[test]
+    fn test_fmt_floats() {
+        const SQL: &str = {
+            let mut buf = ConstString::default();
+            fmt_f64(&mut buf, 1.5);
+            buf.push_ascii(b',');
+            fmt_f64(&mut buf, -0.25);
+            buf.push_ascii(b',');
+            fmt_f64(&mut buf, 0.1);
+            buf.push_ascii(b',');
+            fmt_f64(&mut buf, 3.0);
+            buf.push_ascii(b',');
+            fmt_f64(&mut buf, 0.0000001);
+            buf.push_ascii(b',');
+            fmt_f32(&mut buf, 0.1);
+            buf.leak()
+        };
+
+        assert_eq!(SQL, "1.5,-0.25,0.1,3.0,1E-7,0.1");
+    }
+
+    #[test]
+    fn test_fmt_floats_round_trip() {
+        let mut values = vec![
+            f64::MIN_POSITIVE,
+            f64::from_bits(1),
+            f64::MAX,
+            f64::EPSILON,
+            1e23,
+            5e-324,
+        ];
+
+        for state in xorshift().take(2_000) {
+            let value = f64::from_bits(state & !(1 << 63));
+            if value.is_finite() {
+                values.push(value);
+            }
+        }
+
+        for value in values {
+            let formatted = render(|buf| fmt_f64(buf, value));
+
+            assert_eq!(formatted.parse::<f64>().unwrap(), value, "{formatted}");
+            assert_eq!(std_digits(formatted), std_digits(&format!("{value:e}")));
+        }
+
+        for bits in (1u32..0x7f80_0000).step_by(0x7_1234) {
+            let value = f32::from_bits(bits);
+            let formatted = render(|buf| fmt_f32(buf, value));
+
+            assert_eq!(formatted.parse::<f32>().unwrap(), value, "{formatted}");
+            assert_eq!(std_digits(formatted), std_digits(&format!("{value:e}")));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "NaN and infinite floats have no SQL literal")]
+    fn test_fmt_nan() {
+        render(|buf| fmt_f64(buf, f64::NAN));
+    }
+}
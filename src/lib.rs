@@ -10,6 +10,7 @@
     const_alloc_layout,
     const_box,
     const_eval_select,
+    const_float_bits_conv,
     const_fmt_arguments_new,
     const_heap,
     const_maybe_uninit_uninit_array,
@@ -33,12 +34,16 @@
     marker_trait_attr,
     maybe_uninit_uninit_array,
     slice_ptr_get,
+    specialization,
+    type_changing_struct_update
 )]
 
 pub(crate) mod const_alloc;
 pub(crate) mod const_vec;
 pub(crate) mod const_string;
 pub(crate) mod fmt;
+pub mod ddl;
+pub mod dialect;
 pub mod expression;
 pub mod query;
 pub mod schema;
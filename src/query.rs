@@ -1 +1,114 @@
+use crate::{
+    dialect::Dialect,
+    expression::{Precedence, Sql, SqlExpression},
+};
+
+pub mod compound;
+pub mod delete;
+pub mod insert;
+pub mod merge;
 pub mod select;
+pub mod update;
+pub mod with;
+
+/// A chain of predicates joined by `AND`.
+pub struct Conjunction<Prev, Pred> {
+    prev: Option<Prev>,
+    predicate: Pred,
+}
+
+impl<Prev, Pred> const SqlExpression for Conjunction<Prev, Pred>
+where
+    Prev: ~const SqlExpression,
+    Pred: ~const SqlExpression,
+{
+    const PRECEDENCE: Precedence = Precedence::And;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if let Some(prev) = &self.prev {
+            prev.write_sql_expression(sql);
+            sql.push_str(" AND ");
+        }
+
+        sql.push_predicate(&self.predicate, Precedence::Or);
+    }
+}
+
+/// Writes the `LIMIT` and `OFFSET` clauses of a query, or their equivalent in
+/// the dialect.
+///
+/// SQL Server only supports `OFFSET ... FETCH NEXT ...` in queries with an
+/// `ORDER BY`, as told by `ordered`.
+pub(crate) const fn write_limit_offset(
+    sql: &mut Sql,
+    limit: Option<u64>,
+    offset: Option<u64>,
+    ordered: bool,
+) {
+    if limit.is_none() && offset.is_none() {
+        return;
+    }
+
+    match sql.dialect() {
+        Dialect::Postgres | Dialect::MySql | Dialect::Sqlite => {
+            match (limit, sql.dialect()) {
+                (Some(limit), _) => {
+                    sql.push_str(" LIMIT ").push_u64(limit);
+                }
+                // Neither accepts an `OFFSET` alone.
+                (None, Dialect::MySql) => {
+                    sql.push_str(" LIMIT ").push_u64(u64::MAX);
+                }
+                (None, Dialect::Sqlite) => {
+                    sql.push_str(" LIMIT -1");
+                }
+                (None, _) => {}
+            }
+
+            if let Some(offset) = offset {
+                sql.push_str(" OFFSET ").push_u64(offset);
+            }
+        }
+        Dialect::SqlServer | Dialect::Ansi => {
+            let is_sql_server = matches!(sql.dialect(), Dialect::SqlServer);
+
+            if is_sql_server && !ordered {
+                panic!("SQL Server requires ORDER BY to use OFFSET and FETCH");
+            }
+
+            // SQL Server can't `FETCH` without an `OFFSET`.
+            match offset {
+                Some(offset) => {
+                    sql.push_str(" OFFSET ").push_u64(offset).push_str(" ROWS");
+                }
+                None if is_sql_server => {
+                    sql.push_str(" OFFSET 0 ROWS");
+                }
+                None => {}
+            }
+
+            if let Some(limit) = limit {
+                sql.push_str(" FETCH NEXT ")
+                    .push_u64(limit)
+                    .push_str(" ROWS ONLY");
+            }
+        }
+    }
+}
+
+/// Writes the `RETURNING` clause of a write statement, if any.
+///
+/// Only Postgres and SQLite support it, so rendering it for any other dialect
+/// panics, which is a compile error when rendered in a const context.
+pub(crate) const fn write_returning<Returning>(sql: &mut Sql, returning: &Option<Returning>)
+where
+    Returning: ~const SqlExpression,
+{
+    if let Some(returning) = returning {
+        if !matches!(sql.dialect(), Dialect::Postgres | Dialect::Sqlite) {
+            panic!("RETURNING clauses are only supported by Postgres and SQLite");
+        }
+
+        returning.write_sql_expression(sql.push_str(" RETURNING "));
+    }
+}
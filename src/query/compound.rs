@@ -0,0 +1,345 @@
+//! Set operations combining the rows of two queries, as in
+//! `SELECT ... UNION SELECT ...`.
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{check_arity, Precedence, Sql, SqlExpression},
+    query::write_limit_offset,
+    schema::SqlTypeClass,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SetOperator {
+    Union,
+    UnionAll,
+    Intersect,
+    Except,
+}
+
+impl SetOperator {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            SetOperator::Union => "UNION",
+            SetOperator::UnionAll => "UNION ALL",
+            SetOperator::Intersect => "INTERSECT",
+            SetOperator::Except => "EXCEPT",
+        }
+    }
+}
+
+/// Adds `union`, `union_all`, `intersect` and `except` to a query builder.
+macro_rules! impl_set_operations {
+    () => {
+        $crate::query::compound::impl_set_operations!(
+            union,
+            Union,
+            "Combines the rows of both queries, removing duplicates."
+        );
+        $crate::query::compound::impl_set_operations!(
+            union_all,
+            UnionAll,
+            "Combines the rows of both queries, keeping duplicates."
+        );
+        $crate::query::compound::impl_set_operations!(
+            intersect,
+            Intersect,
+            "Keeps the rows returned by both queries."
+        );
+        $crate::query::compound::impl_set_operations!(
+            except,
+            Except,
+            "Keeps the rows not returned by `other`."
+        );
+    };
+    ($method: ident, $op: ident, $doc: literal) => {
+        #[doc = $doc]
+        ///
+        /// Both queries must return the same number of columns, which is a
+        /// compile error when known from their types. Queries selecting the
+        /// columns of a slice, as [`from`](crate::query::select::from) does,
+        /// are only checked when the compound is built.
+        pub const fn $method<Other>(
+            self,
+            other: Other,
+        ) -> $crate::query::compound::Compound<
+            Self,
+            Other,
+            { $crate::query::compound::SetOperator::$op },
+        >
+        where
+            Self: ~const $crate::expression::SqlExpression,
+            Other: ~const $crate::expression::SqlExpression,
+            [(); $crate::expression::check_arity(Self::ARITY, Other::ARITY)]:,
+        {
+            $crate::query::compound::Compound::new(self, other)
+        }
+    };
+}
+
+pub(crate) use impl_set_operations;
+
+/// Two queries combined by a set operator, with an optional trailing
+/// `ORDER BY`, `OFFSET` and `LIMIT` applying to the combined rows.
+pub struct Compound<Lhs, Rhs, const OP: SetOperator, Order = ()> {
+    lhs: Lhs,
+    rhs: Rhs,
+    order: Option<Order>,
+    limit: Option<u64>,
+    offset: Option<u64>,
+}
+
+impl<Lhs, Rhs, const OP: SetOperator> Compound<Lhs, Rhs, OP> {
+    /// Panics if both queries have a different number of columns, once known.
+    pub(crate) const fn new(lhs: Lhs, rhs: Rhs) -> Self
+    where
+        Lhs: ~const SqlExpression,
+        Rhs: ~const SqlExpression,
+    {
+        check_arity(lhs.arity(), rhs.arity());
+
+        Compound {
+            lhs,
+            rhs,
+            order: None,
+            limit: None,
+            offset: None,
+        }
+    }
+}
+
+impl<Lhs, Rhs, const OP: SetOperator, Order> Compound<Lhs, Rhs, OP, Order> {
+    impl_set_operations!();
+
+    /// Sets the `ORDER BY` clause of the combined rows, replacing any previous
+    /// one.
+    ///
+    /// Terms can only refer to the output columns, usually by their bare
+    /// names, see [`Column::unqualified`](crate::schema::Column::unqualified).
+    pub const fn order_by<O>(self, terms: O) -> Compound<Lhs, Rhs, OP, O>
+    where
+        Self: ~const Destruct,
+    {
+        Compound {
+            order: Some(terms),
+            ..self
+        }
+    }
+
+    pub const fn limit(mut self, limit: u64) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub const fn offset(mut self, offset: u64) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+}
+
+impl<Lhs, Rhs, const OP: SetOperator, Order> const SqlExpression for Compound<Lhs, Rhs, OP, Order>
+where
+    Lhs: ~const SqlExpression,
+    Rhs: ~const SqlExpression,
+    Order: ~const SqlExpression,
+{
+    // `INTERSECT` binds tighter than `UNION` and `EXCEPT`, which are left
+    // associative. Borrowing the boolean levels keeps both below any query.
+    const PRECEDENCE: Precedence = match OP {
+        SetOperator::Intersect => Precedence::And,
+        _ => Precedence::Or,
+    };
+    const CLASS: SqlTypeClass = Lhs::CLASS;
+    const NULLABLE: bool = Lhs::NULLABLE || Rhs::NULLABLE;
+    const ARITY: Option<usize> = Lhs::ARITY;
+    const CLASSES: &'static [SqlTypeClass] = Lhs::CLASSES;
+
+    // SQLite doesn't accept parenthesized operands and gives every operator
+    // the same precedence, so only nested right operands need wrapping.
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let sqlite = matches!(sql.dialect(), Dialect::Sqlite);
+
+        let lhs_nested = !sqlite && Self::PRECEDENCE.binds_tighter_than(Lhs::PRECEDENCE);
+        write_operand(sql, &self.lhs, lhs_nested);
+
+        sql.spacing().push_str(OP.as_str()).spacing();
+
+        let rhs_nested = if sqlite {
+            !matches!(Rhs::PRECEDENCE, Precedence::Atom)
+        } else {
+            !Rhs::PRECEDENCE.binds_tighter_than(Self::PRECEDENCE)
+        };
+        write_operand(sql, &self.rhs, rhs_nested);
+
+        if let Some(order) = &self.order {
+            order.write_sql_expression(sql.push_str(" ORDER BY "));
+        }
+
+        write_limit_offset(sql, self.limit, self.offset, self.order.is_some());
+    }
+
+    fn arity(&self) -> Option<usize> {
+        self.lhs.arity()
+    }
+
+    fn is_ordered_or_limited(&self) -> bool {
+        self.order.is_some() || self.limit.is_some() || self.offset.is_some()
+    }
+}
+
+/// Writes an operand of a set operator, parenthesized when `nested` or when it
+/// has its own `ORDER BY` or `LIMIT`.
+///
+/// SQLite selects from those operands as subqueries instead.
+const fn write_operand<E>(sql: &mut Sql, operand: &E, nested: bool)
+where
+    E: ~const SqlExpression,
+{
+    if !nested && !operand.is_ordered_or_limited() {
+        operand.write_sql_expression(sql);
+    } else if matches!(sql.dialect(), Dialect::Sqlite) {
+        sql.push_str("SELECT * FROM (");
+        operand.write_sql_expression(sql);
+        sql.push(b')');
+    } else {
+        sql.push(b'(');
+        operand.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        query::select::from,
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_set_operations() {
+        static SQL: &str = from(USERS)
+            .select(USERS_ID)
+            .filter(USERS_EMAIL.is_null())
+            .union_all(from(POSTS).select(POSTS_USER_ID))
+            .except(from(EMPLOYEES).select(EMPLOYEES_ID))
+            .order_by(USERS_ID.unqualified().desc())
+            .limit(5)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id FROM public.users WHERE users.email IS NULL \
+             UNION ALL SELECT posts.user_id FROM public.posts \
+             EXCEPT SELECT employees.id FROM hr.employees ORDER BY id DESC LIMIT 5"
+        );
+    }
+
+    #[test]
+    fn test_set_operations_precedence() {
+        static SQL: (u32, &str) = {
+            let sql = from(USERS)
+                .select(USERS_ID)
+                .union(from(POSTS).select(POSTS_USER_ID))
+                .intersect(
+                    from(EMPLOYEES)
+                        .select(EMPLOYEES_ID)
+                        .filter(EMPLOYEES_NAME.eq(Param::<String>::new()))
+                        .intersect(from(EMPLOYEES).select(EMPLOYEES_MANAGER_ID)),
+                )
+                .to_sql();
+
+            (sql.bindings(), sql.into_str())
+        };
+
+        assert_eq!(
+            SQL,
+            (
+                1,
+                "(SELECT users.id FROM public.users UNION \
+                 SELECT posts.user_id FROM public.posts) INTERSECT \
+                 (SELECT employees.id FROM hr.employees WHERE employees.name = $1 \
+                 INTERSECT SELECT employees.manager_id FROM hr.employees)"
+            )
+        );
+    }
+
+    #[test]
+    fn test_ordered_operands() {
+        macro_rules! ordered {
+            () => {
+                from(USERS)
+                    .select(USERS_ID)
+                    .order_by(USERS_ID)
+                    .limit(1)
+                    .union(from(POSTS).select(POSTS_USER_ID))
+                    .union(from(EMPLOYEES).select(EMPLOYEES_ID).limit(2))
+            };
+        }
+
+        static PG: &str = ordered!().to_sql_with(Dialect::Postgres).into_str();
+        static SQLITE: &str = ordered!().to_sql_with(Dialect::Sqlite).into_str();
+
+        assert_eq!(
+            PG,
+            "(SELECT users.id FROM public.users ORDER BY users.id LIMIT 1) UNION \
+             SELECT posts.user_id FROM public.posts UNION \
+             (SELECT employees.id FROM hr.employees LIMIT 2)"
+        );
+        assert_eq!(
+            SQLITE,
+            "SELECT * FROM (SELECT users.id FROM public.users ORDER BY users.id LIMIT 1) UNION \
+             SELECT posts.user_id FROM public.posts UNION \
+             SELECT * FROM (SELECT employees.id FROM hr.employees LIMIT 2)"
+        );
+    }
+
+    #[test]
+    fn test_sqlite_nested_operands() {
+        static SQL: &str = from(USERS)
+            .select(USERS_ID)
+            .union(from(POSTS).select(POSTS_USER_ID))
+            .intersect(
+                from(EMPLOYEES)
+                    .select(EMPLOYEES_ID)
+                    .except(from(EMPLOYEES).select(EMPLOYEES_MANAGER_ID)),
+            )
+            .to_sql_with(Dialect::Sqlite)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id FROM public.users UNION SELECT posts.user_id FROM public.posts \
+             INTERSECT SELECT * FROM (SELECT employees.id FROM hr.employees \
+             EXCEPT SELECT employees.manager_id FROM hr.employees)"
+        );
+    }
+
+    #[test]
+    fn test_set_operation_in_subquery() {
+        static SQL: &str = from(USERS)
+            .filter(
+                USERS_ID.in_(
+                    from(POSTS)
+                        .select(POSTS_USER_ID)
+                        .intersect(from(EMPLOYEES).select(EMPLOYEES_ID)),
+                ),
+            )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id,users.name,users.email FROM public.users WHERE users.id IN \
+             (SELECT posts.user_id FROM public.posts INTERSECT SELECT employees.id FROM hr.employees)"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "set operation operands must return the same number of columns")]
+    fn test_set_operation_slice_arity_mismatch() {
+        from(USERS).union(from(POSTS).select((POSTS_USER_ID, POSTS_TITLE)));
+    }
+}
@@ -0,0 +1,177 @@
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{Sql, SqlExpression},
+    query::{write_returning, Conjunction},
+    schema::Table,
+};
+
+pub const fn from(table: Table) -> Delete {
+    Delete {
+        table,
+        using: None,
+        filter: None,
+        returning: None,
+    }
+}
+
+pub struct Delete<Using = (), Filter = (), Returning = ()> {
+    table: Table,
+    using: Option<Using>,
+    filter: Option<Filter>,
+    returning: Option<Returning>,
+}
+
+impl<Using, Filter, Returning> const SqlExpression for Delete<Using, Filter, Returning>
+where
+    Using: ~const SqlExpression,
+    Filter: ~const SqlExpression,
+    Returning: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("DELETE FROM ");
+        self.table.write_sql_expression(sql);
+
+        if let Some(using) = &self.using {
+            sql.push_str(" USING ");
+
+            match sql.dialect() {
+                Dialect::Sqlite | Dialect::SqlServer => {
+                    panic!("DELETE ... USING is not supported by SQLite and SQL Server");
+                }
+                Dialect::MySql => {
+                    self.table.write_sql_expression(sql);
+                    sql.comma();
+                }
+                Dialect::Postgres | Dialect::Ansi => {}
+            }
+
+            using.write_sql_expression(sql);
+        }
+
+        if let Some(filter) = &self.filter {
+            filter.write_sql_predicate(sql.push_str(" WHERE "));
+        }
+
+        write_returning(sql, &self.returning);
+    }
+}
+
+impl<Using, Filter, Returning> Delete<Using, Filter, Returning> {
+    /// Joins the table with `source`, as in `DELETE FROM ... USING source`.
+    ///
+    /// The join condition goes in the `WHERE` clause. MySQL lists the table
+    /// itself in `USING` too, while rendering it for SQLite and SQL Server
+    /// panics.
+    pub const fn using<U>(self, source: U) -> Delete<U, Filter, Returning>
+    where
+        Self: ~const Destruct,
+    {
+        Delete {
+            using: Some(source),
+            ..self
+        }
+    }
+
+    /// Adds a predicate to the `WHERE` clause.
+    ///
+    /// Calling this more than once `AND`s the predicates together, in the order
+    /// they were added.
+    pub const fn filter<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Delete<Using, Conjunction<Filter, Pred>, Returning> {
+        Delete {
+            filter: Some(Conjunction {
+                prev: self.filter,
+                predicate,
+            }),
+            ..self
+        }
+    }
+
+    /// Alias of [`Delete::filter`].
+    pub const fn where_<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Delete<Using, Conjunction<Filter, Pred>, Returning> {
+        self.filter(predicate)
+    }
+
+    /// Returns `projections` from the deleted rows.
+    pub const fn returning<P>(self, projections: P) -> Delete<Using, Filter, P>
+    where
+        Self: ~const Destruct,
+    {
+        Delete {
+            returning: Some(projections),
+            ..self
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_delete() {
+        static ALL: &str = from(POSTS).to_sql().into_str();
+        static FILTERED: &str = from(POSTS)
+            .filter(POSTS_ID.lt(Param::<i64>::new()))
+            .returning((POSTS_ID, POSTS_TITLE))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(ALL, "DELETE FROM public.posts");
+        assert_eq!(
+            FILTERED,
+            "DELETE FROM public.posts WHERE posts.id < $1 RETURNING posts.id,posts.title"
+        );
+    }
+
+    #[test]
+    fn test_delete_using() {
+        static SQL: &str = from(POSTS)
+            .using(USERS)
+            .filter(POSTS_USER_ID.eq(USERS_ID))
+            .where_(USERS_EMAIL.is_null())
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "DELETE FROM public.posts USING public.users \
+             WHERE posts.user_id = users.id AND users.email IS NULL"
+        );
+    }
+
+    #[test]
+    fn test_delete_using_mysql() {
+        static SQL: &str = from(POSTS)
+            .using(USERS)
+            .filter(POSTS_USER_ID.eq(USERS_ID))
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "DELETE FROM public.posts USING public.posts,public.users \
+             WHERE posts.user_id = users.id"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DELETE ... USING is not supported by SQLite and SQL Server")]
+    fn test_delete_using_sql_server() {
+        from(POSTS)
+            .using(USERS)
+            .filter(POSTS_USER_ID.eq(USERS_ID))
+            .to_sql_with(Dialect::SqlServer);
+    }
+}
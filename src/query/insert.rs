@@ -0,0 +1,563 @@
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{check_assignable, check_nullable, check_row, Sql, SqlExpression},
+    query::{update::Assignments, write_returning, Conjunction},
+    schema::{Column, ColumnNames, SqlTypeClass, Table},
+};
+
+pub const fn insert_into(table: Table) -> InsertInto<()> {
+    InsertInto {
+        table,
+        columns: None,
+    }
+}
+
+/// An `INSERT` statement still missing the rows to be inserted.
+pub struct InsertInto<Cols> {
+    table: Table,
+    columns: Option<Cols>,
+}
+
+impl InsertInto<()> {
+    /// Sets the columns the inserted rows will fill, in order.
+    pub const fn columns<Cols>(self, columns: Cols) -> InsertInto<Cols> {
+        InsertInto {
+            table: self.table,
+            columns: Some(columns),
+        }
+    }
+
+    /// Inserts a single row filled with the columns defaults.
+    pub const fn default_values(self) -> Insert<(), DefaultValues> {
+        Insert {
+            table: self.table,
+            columns: None,
+            source: DefaultValues,
+            conflict: (),
+            returning: None,
+        }
+    }
+}
+
+impl<Cols: ColumnNames> InsertInto<Cols> {
+    /// Inserts a single row, usually a tuple of expressions.
+    ///
+    /// The row must have as many values as there are columns, each of a class
+    /// assignable to its column, which is checked at compile time.
+    pub const fn values<Row>(self, row: Row) -> Insert<Cols, Values<Row, 1>>
+    where
+        Row: SqlExpression,
+        [(); check_row(Cols::ARITY, Cols::CLASSES, Row::ARITY, Row::CLASSES)]:,
+    {
+        self.values_rows([row])
+    }
+
+    /// Inserts `N` rows at once.
+    ///
+    /// As each row is rendered separately, repeating a row of
+    /// [`Param`](crate::expression::param::Param)s gives each row its own
+    /// set of placeholders.
+    pub const fn values_rows<Row, const N: usize>(
+        self,
+        rows: [Row; N],
+    ) -> Insert<Cols, Values<Row, N>>
+    where
+        Row: SqlExpression,
+        [(); check_row(Cols::ARITY, Cols::CLASSES, Row::ARITY, Row::CLASSES)]:,
+    {
+        Insert {
+            table: self.table,
+            columns: self.columns,
+            source: Values { rows },
+            conflict: (),
+            returning: None,
+        }
+    }
+
+    /// Inserts the rows returned by `query`, usually a
+    /// [`Select`](crate::query::select::Select), checked like
+    /// [`InsertInto::values`].
+    pub const fn select<Query>(self, query: Query) -> Insert<Cols, Query>
+    where
+        Query: SqlExpression,
+        [(); check_row(Cols::ARITY, Cols::CLASSES, Query::ARITY, Query::CLASSES)]:,
+    {
+        Insert {
+            table: self.table,
+            columns: self.columns,
+            source: query,
+            conflict: (),
+            returning: None,
+        }
+    }
+}
+
+/// An `INSERT` statement.
+///
+/// `Conflict` is either `()` or the [`OnConflict`] clause of an upsert.
+pub struct Insert<Cols, Source, Conflict = (), Returning = ()> {
+    table: Table,
+    columns: Option<Cols>,
+    source: Source,
+    conflict: Conflict,
+    returning: Option<Returning>,
+}
+
+impl<Cols, Source, Conflict, Returning> Insert<Cols, Source, Conflict, Returning> {
+    /// Returns `projections` from the inserted rows, as in generated keys.
+    pub const fn returning<P>(self, projections: P) -> Insert<Cols, Source, Conflict, P>
+    where
+        Self: ~const Destruct,
+    {
+        Insert {
+            returning: Some(projections),
+            ..self
+        }
+    }
+}
+
+impl<Cols, Source, Returning> Insert<Cols, Source, (), Returning> {
+    /// Turns the insert into an upsert, handling the rows that conflict with
+    /// existing ones on `target`, the columns of a unique index.
+    ///
+    /// Renders `ON CONFLICT (target)` for Postgres and SQLite and
+    /// `ON DUPLICATE KEY` for MySQL, which ignores `target` and checks every
+    /// unique index instead. Other dialects must use
+    /// [`merge_into`](crate::query::merge::merge_into).
+    pub const fn on_conflict<Target>(
+        self,
+        target: Target,
+    ) -> IncompleteInsertConflict<Cols, Source, Target, Returning> {
+        IncompleteInsertConflict {
+            insert: self,
+            target,
+        }
+    }
+}
+
+impl<Cols, Source, Target, Assign, Filter, Returning>
+    UpsertUpdate<Cols, Source, Target, Assign, Filter, Returning>
+{
+    /// Adds a `column = value` assignment to the `DO UPDATE SET` clause.
+    pub const fn set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> UpsertUpdate<Cols, Source, Target, Assignments<Assign, Value>, Filter, Returning>
+    where
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        let OnConflict { target, action } = self.conflict;
+
+        Insert {
+            conflict: OnConflict {
+                target,
+                action: DoUpdate {
+                    assignments: Assignments::new(Some(action.assignments), column, value),
+                    filter: action.filter,
+                },
+            },
+            ..self
+        }
+    }
+
+    /// Adds a predicate to the `WHERE` clause of `DO UPDATE`, skipping the
+    /// update of the conflicting rows not matching it.
+    ///
+    /// Calling this more than once `AND`s the predicates together, in the order
+    /// they were added.
+    pub const fn filter<Pred>(
+        self,
+        predicate: Pred,
+    ) -> UpsertUpdate<Cols, Source, Target, Assign, Conjunction<Filter, Pred>, Returning> {
+        let OnConflict { target, action } = self.conflict;
+
+        Insert {
+            conflict: OnConflict {
+                target,
+                action: DoUpdate {
+                    assignments: action.assignments,
+                    filter: Some(Conjunction {
+                        prev: action.filter,
+                        predicate,
+                    }),
+                },
+            },
+            ..self
+        }
+    }
+}
+
+impl<Cols, Source, Conflict, Returning> const SqlExpression
+    for Insert<Cols, Source, Conflict, Returning>
+where
+    Cols: ~const ColumnNames,
+    Source: ~const SqlExpression,
+    Conflict: ~const SqlExpression,
+    Returning: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("INSERT INTO ");
+        self.table.write_sql_expression(sql);
+
+        if let Some(columns) = &self.columns {
+            sql.push_str(" (");
+            columns.write_column_names(sql);
+            sql.push(b')');
+        }
+
+        self.source.write_sql_expression(sql.spacing());
+        self.conflict.write_sql_expression(sql);
+        write_returning(sql, &self.returning);
+    }
+}
+
+/// The [`Insert`] updating the rows conflicting on `Target`.
+type UpsertUpdate<Cols, Source, Target, Assign, Filter, Returning> =
+    Insert<Cols, Source, OnConflict<Target, DoUpdate<Assign, Filter>>, Returning>;
+
+/// An upsert still missing the action to take on conflicting rows.
+pub struct IncompleteInsertConflict<Cols, Source, Target, Returning> {
+    insert: Insert<Cols, Source, (), Returning>,
+    target: Target,
+}
+
+impl<Cols, Source, Target, Returning> IncompleteInsertConflict<Cols, Source, Target, Returning> {
+    /// Skips the conflicting rows.
+    pub const fn do_nothing(
+        self,
+    ) -> Insert<Cols, Source, OnConflict<Target, DoNothing>, Returning> {
+        Insert {
+            conflict: OnConflict {
+                target: self.target,
+                action: DoNothing,
+            },
+            ..self.insert
+        }
+    }
+
+    /// Updates the existing rows instead, starting with a `column = value`
+    /// assignment.
+    ///
+    /// The values of the row that failed to insert are available through
+    /// [`excluded`].
+    pub const fn do_update_set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> UpsertUpdate<Cols, Source, Target, Assignments<(), Value>, (), Returning>
+    where
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        Insert {
+            conflict: OnConflict {
+                target: self.target,
+                action: DoUpdate {
+                    assignments: Assignments::new(None, column, value),
+                    filter: None,
+                },
+            },
+            ..self.insert
+        }
+    }
+}
+
+/// The conflict handling of an upsert, see [`Insert::on_conflict`].
+pub struct OnConflict<Target, Action> {
+    target: Target,
+    action: Action,
+}
+
+impl<Target, Action> const SqlExpression for OnConflict<Target, Action>
+where
+    Target: ~const ColumnNames,
+    Action: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match sql.dialect() {
+            Dialect::Postgres | Dialect::Sqlite => {
+                sql.push_str(" ON CONFLICT (");
+                self.target.write_column_names(sql);
+                sql.push_str(") ");
+            }
+            Dialect::MySql => {
+                sql.push_str(" ON DUPLICATE KEY ");
+            }
+            Dialect::SqlServer | Dialect::Ansi => {
+                panic!("SQL Server and ANSI SQL upserts must use MERGE");
+            }
+        }
+
+        self.action.write_sql_expression(sql);
+    }
+}
+
+/// `DO NOTHING`.
+pub struct DoNothing;
+
+impl const SqlExpression for DoNothing {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if matches!(sql.dialect(), Dialect::MySql) {
+            panic!("MySQL can't skip conflicting rows, use INSERT IGNORE");
+        }
+
+        sql.push_str("DO NOTHING");
+    }
+}
+
+/// `DO UPDATE SET ... WHERE ...`, or `UPDATE ...` for MySQL.
+pub struct DoUpdate<Assign, Filter = ()> {
+    assignments: Assign,
+    filter: Option<Filter>,
+}
+
+impl<Assign, Filter> const SqlExpression for DoUpdate<Assign, Filter>
+where
+    Assign: ~const SqlExpression,
+    Filter: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if matches!(sql.dialect(), Dialect::MySql) {
+            if self.filter.is_some() {
+                panic!("MySQL upserts can't filter the updated rows");
+            }
+
+            self.assignments
+                .write_sql_expression(sql.push_str("UPDATE "));
+            return;
+        }
+
+        self.assignments
+            .write_sql_expression(sql.push_str("DO UPDATE SET "));
+
+        if let Some(filter) = &self.filter {
+            filter.write_sql_predicate(sql.push_str(" WHERE "));
+        }
+    }
+}
+
+/// The value `column` would have had in the row that failed to insert, inside
+/// [`IncompleteInsertConflict::do_update_set`].
+///
+/// Renders `EXCLUDED.column`, or `VALUES(column)` for MySQL.
+pub const fn excluded<const C: SqlTypeClass, const NULLABLE: bool>(
+    column: Column<C, NULLABLE>,
+) -> Excluded<C, NULLABLE> {
+    Excluded(column)
+}
+
+/// See [`excluded`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Excluded<const C: SqlTypeClass = { SqlTypeClass::Unknown }, const NULLABLE: bool = true>(
+    pub Column<C, NULLABLE>,
+);
+
+impl<const C: SqlTypeClass, const NULLABLE: bool> const SqlExpression for Excluded<C, NULLABLE> {
+    const CLASS: SqlTypeClass = C;
+    const NULLABLE: bool = NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if matches!(sql.dialect(), Dialect::MySql) {
+            sql.push_str("VALUES(").push_ident(self.0.name).push(b')');
+        } else {
+            sql.push_str("EXCLUDED.").push_ident(self.0.name);
+        }
+    }
+}
+
+/// `VALUES (...),(...)` with `N` rows.
+pub struct Values<Row, const N: usize> {
+    rows: [Row; N],
+}
+
+impl<Row, const N: usize> const SqlExpression for Values<Row, N>
+where
+    Row: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("VALUES ");
+
+        let mut idx = 0;
+        while idx < N {
+            if idx > 0 {
+                sql.comma();
+            }
+
+            sql.push(b'(');
+            self.rows[idx].write_sql_expression(sql);
+            sql.push(b')');
+
+            idx += 1;
+        }
+    }
+}
+
+/// `DEFAULT VALUES`.
+pub struct DefaultValues;
+
+impl const SqlExpression for DefaultValues {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("DEFAULT VALUES");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        dialect::Dialect,
+        expression::{ops::SqlExpressionExt, param::Param},
+        query::select::from,
+        schema::{fixtures::*, table_columns},
+    };
+
+    #[test]
+    fn test_insert_values() {
+        static SQL: &str = insert_into(USERS)
+            .columns((USERS_ID, USERS_NAME, USERS_EMAIL))
+            .values((
+                Param::<i64>::new(),
+                Param::<String>::new(),
+                Param::<String>::new(),
+            ))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "INSERT INTO public.users (id,name,email) VALUES ($1,$2,$3)"
+        );
+    }
+
+    #[test]
+    fn test_insert_multiple_rows() {
+        static SQL: &str = insert_into(POSTS)
+            .columns((POSTS_USER_ID, POSTS_TITLE))
+            .values_rows([(Param::<i64>::new(), Param::<String>::new()); 3])
+            .to_sql_with(Dialect::MySql)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "INSERT INTO public.posts (user_id,title) VALUES (?,?),(?,?),(?,?)"
+        );
+    }
+
+    #[test]
+    fn test_insert_select_and_default_values() {
+        static SELECT: &str = insert_into(POSTS)
+            .columns((POSTS_USER_ID, POSTS_TITLE))
+            .select(
+                from(USERS)
+                    .select((USERS_ID, USERS_NAME))
+                    .filter(USERS_EMAIL.is_null()),
+            )
+            .to_sql()
+            .into_str();
+        static DEFAULT: &str = insert_into(USERS).default_values().to_sql().into_str();
+
+        assert_eq!(
+            SELECT,
+            "INSERT INTO public.posts (user_id,title) SELECT users.id,users.name \
+             FROM public.users WHERE users.email IS NULL"
+        );
+        assert_eq!(DEFAULT, "INSERT INTO public.users DEFAULT VALUES");
+    }
+
+    #[test]
+    fn test_insert_returning() {
+        static SQL: &str = insert_into(POSTS)
+            .columns((POSTS_USER_ID, POSTS_TITLE))
+            .values((Param::<i64>::new(), Param::<String>::new()))
+            .returning(POSTS_ID)
+            .to_sql()
+            .into_str();
+        static SQLITE: &str = insert_into(USERS)
+            .default_values()
+            .returning(table_columns(USERS))
+            .to_sql_with(Dialect::Sqlite)
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "INSERT INTO public.posts (user_id,title) VALUES ($1,$2) RETURNING posts.id"
+        );
+        assert_eq!(
+            SQLITE,
+            "INSERT INTO public.users DEFAULT VALUES RETURNING users.id,users.name,users.email"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "RETURNING clauses are only supported by Postgres and SQLite")]
+    fn test_insert_returning_unsupported() {
+        insert_into(USERS)
+            .default_values()
+            .returning(USERS_ID)
+            .to_sql_with(Dialect::MySql);
+    }
+
+    #[test]
+    fn test_upsert() {
+        macro_rules! upsert {
+            () => {
+                insert_into(USERS)
+                    .columns((USERS_ID, USERS_NAME, USERS_EMAIL))
+                    .values((
+                        Param::<i64>::new(),
+                        Param::<String>::new(),
+                        Param::<String>::new(),
+                    ))
+                    .on_conflict(USERS_ID)
+                    .do_update_set(USERS_NAME, excluded(USERS_NAME))
+                    .set(USERS_EMAIL, excluded(USERS_EMAIL))
+            };
+        }
+
+        static SQL: [&str; 3] = [
+            upsert!()
+                .filter(USERS_EMAIL.is_null())
+                .returning(USERS_ID)
+                .to_sql()
+                .into_str(),
+            upsert!().to_sql_with(Dialect::MySql).into_str(),
+            insert_into(POSTS)
+                .columns((POSTS_USER_ID, POSTS_TITLE))
+                .values((Param::<i64>::new(), Param::<String>::new()))
+                .on_conflict((POSTS_USER_ID, POSTS_TITLE))
+                .do_nothing()
+                .to_sql_with(Dialect::Sqlite)
+                .into_str(),
+        ];
+
+        assert_eq!(
+            SQL,
+            [
+                "INSERT INTO public.users (id,name,email) VALUES ($1,$2,$3) \
+                 ON CONFLICT (id) DO UPDATE SET name = EXCLUDED.name,email = EXCLUDED.email \
+                 WHERE users.email IS NULL RETURNING users.id",
+                "INSERT INTO public.users (id,name,email) VALUES (?,?,?) \
+                 ON DUPLICATE KEY UPDATE name = VALUES(name),email = VALUES(email)",
+                "INSERT INTO public.posts (user_id,title) VALUES (?,?) \
+                 ON CONFLICT (user_id,title) DO NOTHING",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "SQL Server and ANSI SQL upserts must use MERGE")]
+    fn test_upsert_unsupported() {
+        insert_into(USERS)
+            .columns(USERS_NAME)
+            .values(Param::<String>::new())
+            .on_conflict(USERS_NAME)
+            .do_nothing()
+            .to_sql_with(Dialect::SqlServer);
+    }
+}
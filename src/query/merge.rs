@@ -0,0 +1,370 @@
+//! `MERGE INTO ... USING ... ON ...`, inserting, updating or deleting the rows
+//! of a table depending on whether they match the rows of a source.
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{check_assignable, check_nullable, check_row, Sql, SqlExpression},
+    query::update::Assignments,
+    schema::{Column, ColumnNames, SqlTypeClass, Table},
+};
+
+pub const fn merge_into(table: Table) -> MergeInto {
+    MergeInto { table }
+}
+
+/// A `MERGE` statement still missing its source.
+pub struct MergeInto {
+    table: Table,
+}
+
+impl MergeInto {
+    /// Merges the rows of `source`, a table or a derived table.
+    pub const fn using<Source>(self, source: Source) -> MergeUsing<Source> {
+        MergeUsing {
+            table: self.table,
+            source,
+        }
+    }
+}
+
+/// A `MERGE` statement still missing its join condition.
+pub struct MergeUsing<Source> {
+    table: Table,
+    source: Source,
+}
+
+impl<Source> MergeUsing<Source> {
+    /// Matches the rows of the table and the source satisfying `on`.
+    pub const fn on<On>(self, on: On) -> Merge<Source, On> {
+        Merge {
+            table: self.table,
+            source: self.source,
+            on,
+            clauses: (),
+        }
+    }
+}
+
+/// A `MERGE` statement and its `WHEN` clauses, tried in order for each row.
+///
+/// It can only be rendered once it has at least one clause.
+pub struct Merge<Source, On, Clauses = ()> {
+    table: Table,
+    source: Source,
+    on: On,
+    clauses: Clauses,
+}
+
+impl<Source, On, Clauses> Merge<Source, On, Clauses> {
+    /// Starts a `WHEN MATCHED` clause, for the rows of the table matching a
+    /// row of the source.
+    pub const fn when_matched(self) -> WhenMatched<Source, On, Clauses, ()> {
+        WhenMatched {
+            merge: self,
+            condition: None,
+        }
+    }
+
+    /// Starts a `WHEN MATCHED AND condition` clause.
+    pub const fn when_matched_and<Cond>(
+        self,
+        condition: Cond,
+    ) -> WhenMatched<Source, On, Clauses, Cond> {
+        WhenMatched {
+            merge: self,
+            condition: Some(condition),
+        }
+    }
+
+    /// Starts a `WHEN NOT MATCHED` clause, for the rows of the source not
+    /// matching any row of the table.
+    pub const fn when_not_matched(self) -> WhenNotMatched<Source, On, Clauses, ()> {
+        WhenNotMatched {
+            merge: self,
+            condition: None,
+        }
+    }
+
+    /// Starts a `WHEN NOT MATCHED AND condition` clause.
+    pub const fn when_not_matched_and<Cond>(
+        self,
+        condition: Cond,
+    ) -> WhenNotMatched<Source, On, Clauses, Cond> {
+        WhenNotMatched {
+            merge: self,
+            condition: Some(condition),
+        }
+    }
+
+    const fn clause<Cond, Action>(
+        self,
+        matched: bool,
+        condition: Option<Cond>,
+        action: Action,
+    ) -> Merge<Source, On, MergeClause<Clauses, Cond, Action>>
+    where
+        Self: ~const Destruct,
+    {
+        Merge {
+            clauses: MergeClause {
+                prev: self.clauses,
+                matched,
+                condition,
+                action,
+            },
+            ..self
+        }
+    }
+}
+
+impl<Source, On, Prev, Cond, Assign> MergeUpdating<Source, On, Prev, Cond, Assign> {
+    /// Adds a `column = value` assignment to the last `THEN UPDATE SET`
+    /// clause.
+    pub const fn set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> MergeUpdating<Source, On, Prev, Cond, Assignments<Assign, Value>>
+    where
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        let MergeClause {
+            prev,
+            matched,
+            condition,
+            action,
+        } = self.clauses;
+
+        Merge {
+            clauses: MergeClause {
+                prev,
+                matched,
+                condition,
+                action: MergeUpdate(Assignments::new(Some(action.0), column, value)),
+            },
+            ..self
+        }
+    }
+}
+
+impl<Source, On, Prev, Cond, Action> const SqlExpression
+    for Merge<Source, On, MergeClause<Prev, Cond, Action>>
+where
+    Source: ~const SqlExpression,
+    On: ~const SqlExpression,
+    MergeClause<Prev, Cond, Action>: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if matches!(sql.dialect(), Dialect::MySql | Dialect::Sqlite) {
+            panic!("MERGE is not supported by MySQL and SQLite");
+        }
+
+        sql.push_str("MERGE INTO ");
+        self.table.write_sql_expression(sql);
+        self.source.write_sql_expression(sql.push_str(" USING "));
+        self.on.write_sql_predicate(sql.push_str(" ON "));
+        self.clauses.write_sql_expression(sql);
+
+        // SQL Server rejects a `MERGE` without its terminator.
+        if matches!(sql.dialect(), Dialect::SqlServer) {
+            sql.push(b';');
+        }
+    }
+}
+
+/// The [`Merge`] whose last clause is `THEN UPDATE SET`.
+type MergeUpdating<Source, On, Prev, Cond, Assign> =
+    Merge<Source, On, MergeClause<Prev, Cond, MergeUpdate<Assign>>>;
+
+/// A `WHEN MATCHED` clause still missing its action.
+pub struct WhenMatched<Source, On, Clauses, Cond> {
+    merge: Merge<Source, On, Clauses>,
+    condition: Option<Cond>,
+}
+
+impl<Source, On, Clauses, Cond> WhenMatched<Source, On, Clauses, Cond> {
+    /// Updates the matched row, starting with a `column = value` assignment.
+    ///
+    /// The columns of the source can be used in `value`.
+    pub const fn then_update_set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> MergeUpdating<Source, On, Clauses, Cond, Assignments<(), Value>>
+    where
+        Merge<Source, On, Clauses>: ~const Destruct,
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        self.merge.clause(
+            true,
+            self.condition,
+            MergeUpdate(Assignments::new(None, column, value)),
+        )
+    }
+
+    /// Deletes the matched row.
+    pub const fn then_delete(self) -> Merge<Source, On, MergeClause<Clauses, Cond, MergeDelete>>
+    where
+        Merge<Source, On, Clauses>: ~const Destruct,
+    {
+        self.merge.clause(true, self.condition, MergeDelete)
+    }
+}
+
+/// A `WHEN NOT MATCHED` clause still missing its action.
+pub struct WhenNotMatched<Source, On, Clauses, Cond> {
+    merge: Merge<Source, On, Clauses>,
+    condition: Option<Cond>,
+}
+
+impl<Source, On, Clauses, Cond> WhenNotMatched<Source, On, Clauses, Cond> {
+    /// Inserts a row filling `columns` with `row`, usually a tuple of
+    /// expressions on the source columns, checked like
+    /// [`InsertInto::values`](crate::query::insert::InsertInto::values).
+    pub const fn then_insert<Cols, Row>(
+        self,
+        columns: Cols,
+        row: Row,
+    ) -> Merge<Source, On, MergeClause<Clauses, Cond, MergeInsert<Cols, Row>>>
+    where
+        Merge<Source, On, Clauses>: ~const Destruct,
+        Cols: ColumnNames,
+        Row: SqlExpression,
+        [(); check_row(Cols::ARITY, Cols::CLASSES, Row::ARITY, Row::CLASSES)]:,
+    {
+        self.merge
+            .clause(false, self.condition, MergeInsert { columns, row })
+    }
+}
+
+/// The `WHEN` clauses of a [`Merge`], one per `then_*` call.
+pub struct MergeClause<Prev, Cond, Action> {
+    prev: Prev,
+    matched: bool,
+    condition: Option<Cond>,
+    action: Action,
+}
+
+impl<Prev, Cond, Action> const SqlExpression for MergeClause<Prev, Cond, Action>
+where
+    Prev: ~const SqlExpression,
+    Cond: ~const SqlExpression,
+    Action: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.prev.write_sql_expression(sql);
+
+        if self.matched {
+            sql.push_str(" WHEN MATCHED");
+        } else {
+            sql.push_str(" WHEN NOT MATCHED");
+        }
+
+        if let Some(condition) = &self.condition {
+            condition.write_sql_predicate(sql.push_str(" AND "));
+        }
+
+        self.action.write_sql_expression(sql.push_str(" THEN "));
+    }
+}
+
+/// `UPDATE SET ...`.
+pub struct MergeUpdate<Assign>(Assign);
+
+impl<Assign> const SqlExpression for MergeUpdate<Assign>
+where
+    Assign: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.0.write_sql_expression(sql.push_str("UPDATE SET "));
+    }
+}
+
+/// `DELETE`.
+pub struct MergeDelete;
+
+impl const SqlExpression for MergeDelete {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("DELETE");
+    }
+}
+
+/// `INSERT (...) VALUES (...)`.
+pub struct MergeInsert<Cols, Row> {
+    columns: Cols,
+    row: Row,
+}
+
+impl<Cols, Row> const SqlExpression for MergeInsert<Cols, Row>
+where
+    Cols: ~const ColumnNames,
+    Row: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("INSERT (");
+        self.columns.write_column_names(sql);
+        sql.push_str(") VALUES (");
+        self.row.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_merge() {
+        static SQL: [&str; 2] = {
+            let merge = merge_into(USERS)
+                .using(EMPLOYEES)
+                .on(USERS_ID.eq(EMPLOYEES_ID))
+                .when_matched_and(EMPLOYEES_MANAGER_ID.is_null())
+                .then_delete()
+                .when_matched()
+                .then_update_set(USERS_NAME, EMPLOYEES_NAME)
+                .set(USERS_EMAIL, Param::<String>::new())
+                .when_not_matched()
+                .then_insert((USERS_ID, USERS_NAME), (EMPLOYEES_ID, EMPLOYEES_NAME));
+
+            [
+                merge.to_sql().into_str(),
+                merge.to_sql_with(Dialect::SqlServer).into_str(),
+            ]
+        };
+
+        assert_eq!(
+            SQL,
+            [
+                "MERGE INTO public.users USING hr.employees ON users.id = employees.id \
+                 WHEN MATCHED AND employees.manager_id IS NULL THEN DELETE \
+                 WHEN MATCHED THEN UPDATE SET name = employees.name,email = $1 \
+                 WHEN NOT MATCHED THEN INSERT (id,name) VALUES (employees.id,employees.name)",
+                "MERGE INTO [public].users USING hr.employees ON users.id = employees.id \
+                 WHEN MATCHED AND employees.manager_id IS NULL THEN DELETE \
+                 WHEN MATCHED THEN UPDATE SET name = employees.name,email = @p1 \
+                 WHEN NOT MATCHED THEN INSERT (id,name) VALUES (employees.id,employees.name);",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "MERGE is not supported by MySQL and SQLite")]
+    fn test_merge_unsupported() {
+        merge_into(USERS)
+            .using(POSTS)
+            .on(USERS_ID.eq(POSTS_USER_ID))
+            .when_matched()
+            .then_delete()
+            .to_sql_with(Dialect::Sqlite);
+    }
+}
@@ -1,77 +1,349 @@
-use std::marker::Destruct;
+use std::marker::{Destruct, PhantomData};
 
 use crate::{
-    expression::{Sql, SqlExpression},
-    schema::{table_columns, Table, Column},
+    dialect::Dialect,
+    expression::{window::WindowDefinitions, Sql, SqlExpression, Star},
+    query::{compound::impl_set_operations, write_limit_offset, Conjunction},
+    schema::{table_columns, AliasedColumns, AliasedTable, Column, SqlTypeClass, Table},
 };
 
 pub const fn from(table: Table) -> Select<Table, &'static [Column]> {
-    Select {
-        from: table,
-        projections: table_columns(table),
-        limit: None,
-        offset: None,
-    }
+    Select::new(table, table_columns(table))
+}
+
+/// Like [`from`], but referring to the table by its alias.
+pub const fn from_aliased(table: AliasedTable) -> Select<AliasedTable, AliasedColumns> {
+    Select::new(table, table.columns())
 }
 
-pub struct Select<Source, Proj> {
+/// Selects from a subquery, as in `SELECT * FROM (SELECT ...) AS alias`.
+///
+/// See [`Select::alias`].
+pub const fn from_subquery<Query>(table: DerivedTable<Query>) -> Select<DerivedTable<Query>, Star> {
+    Select::new(table, Star)
+}
+
+pub struct Select<
+    Source,
+    Proj,
+    Filter = (),
+    Order = (),
+    Group = (),
+    Having = (),
+    DistinctOn = (),
+    Windows = (),
+> {
     from: Source,
     projections: Proj,
+    filter: Option<Filter>,
+    order: Option<Order>,
+    group: Option<Group>,
+    having: Option<Having>,
+    distinct: Option<Distinct<DistinctOn>>,
+    windows: Option<Windows>,
     limit: Option<u64>,
     offset: Option<u64>,
 }
 
-impl<Source, Proj> const SqlExpression for Select<Source, Proj>
+impl<Source, Proj, Filter, Order, Group, Having, DistinctOn, Windows> const SqlExpression
+    for Select<Source, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
 where
     Source: ~const SqlExpression,
     Proj: ~const SqlExpression,
+    Filter: ~const SqlExpression,
+    Order: ~const SqlExpression,
+    Group: ~const SqlExpression,
+    Having: ~const SqlExpression,
+    DistinctOn: ~const SqlExpression,
+    Windows: ~const SqlExpression,
 {
+    const CLASS: SqlTypeClass = Proj::CLASS;
+    /// Columns can't be traced back to their table at compile time, so all the
+    /// projections are nullable when any table is on the outer side of a join.
+    const NULLABLE: bool = Proj::NULLABLE || Source::NULLABLE;
+    const ARITY: Option<usize> = Proj::ARITY;
+    const CLASSES: &'static [SqlTypeClass] = Proj::CLASSES;
+
     fn write_sql_expression(&self, sql: &mut Sql) {
         sql.push_str("SELECT ");
 
+        match &self.distinct {
+            Some(Distinct::Rows) => {
+                sql.push_str("DISTINCT ");
+            }
+            Some(Distinct::On(on)) => {
+                if !matches!(sql.dialect(), Dialect::Postgres) {
+                    panic!("DISTINCT ON is only supported by Postgres");
+                }
+
+                sql.push_str("DISTINCT ON (");
+                on.write_sql_expression(sql);
+                sql.push_str(") ");
+            }
+            None => {}
+        }
+
         self.projections.write_sql_expression(sql);
 
         sql.push_str(" FROM ");
         self.from.write_sql_expression(sql);
 
-        if let Some(offset) = self.offset {
-            sql.push_str(" OFFSET ").push_u64(offset);
+        if let Some(filter) = &self.filter {
+            filter.write_sql_predicate(sql.push_str(" WHERE "));
+        }
+
+        if let Some(group) = &self.group {
+            group.write_sql_expression(sql.push_str(" GROUP BY "));
         }
 
-        if let Some(limit) = self.limit {
-            sql.push_str(" LIMIT ").push_u64(limit);
+        if let Some(having) = &self.having {
+            having.write_sql_predicate(sql.push_str(" HAVING "));
+        }
+
+        if let Some(windows) = &self.windows {
+            windows.write_sql_expression(sql.push_str(" WINDOW "));
+        }
+
+        if let Some(order) = &self.order {
+            order.write_sql_expression(sql.push_str(" ORDER BY "));
+        }
+
+        write_limit_offset(sql, self.limit, self.offset, self.order.is_some());
+    }
+
+    fn arity(&self) -> Option<usize> {
+        self.projections.arity()
+    }
+
+    fn is_ordered_or_limited(&self) -> bool {
+        self.order.is_some() || self.limit.is_some() || self.offset.is_some()
+    }
+}
+
+impl<Source, Proj> Select<Source, Proj> {
+    const fn new(from: Source, projections: Proj) -> Self {
+        Select {
+            from,
+            projections,
+            filter: None,
+            order: None,
+            group: None,
+            having: None,
+            distinct: None,
+            windows: None,
+            limit: None,
+            offset: None,
         }
     }
 }
 
 macro_rules! impl_join {
+    ($method: ident, $style: ident, unconditional) => {
+        pub const fn $method<Rhs>(
+            self,
+            rhs: Rhs,
+        ) -> JoinedSelect<
+            Source,
+            Rhs,
+            (),
+            { JoinStyle::$style },
+            Proj,
+            Filter,
+            Order,
+            Group,
+            Having,
+            DistinctOn,
+            Windows,
+        > {
+            IncompleteSelectJoin {
+                select: self,
+                right: rhs,
+            }
+            .construct(JoinOn::Unconditional)
+        }
+    };
     ($method: ident, $style: ident) => {
-        pub const fn $method<Rhs>(self, rhs: Rhs) -> IncompleteSelectJoin<Source, Rhs, Proj> {
+        pub const fn $method<Rhs>(
+            self,
+            rhs: Rhs,
+        ) -> IncompleteSelectJoin<
+            Source,
+            Rhs,
+            { JoinStyle::$style },
+            Proj,
+            Filter,
+            Order,
+            Group,
+            Having,
+            DistinctOn,
+            Windows,
+        > {
             IncompleteSelectJoin {
                 select: self,
                 right: rhs,
-                style: JoinStyle::$style
             }
         }
     };
 }
 
-impl<Source, Proj> Select<Source, Proj> {
-    pub const fn select<P>(self, projections: P) -> Select<Source, P>
+impl<Source, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    Select<Source, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+{
+    pub const fn select<P>(
+        self,
+        projections: P,
+    ) -> Select<Source, P, Filter, Order, Group, Having, DistinctOn, Windows>
     where
         Self: ~const Destruct,
     {
         Select {
-            from: self.from,
             projections,
-            limit: self.limit,
-            offset: self.offset,
+            ..self
+        }
+    }
+
+    /// Removes duplicated rows from the results.
+    pub const fn distinct(self) -> Self
+    where
+        Self: ~const Destruct,
+    {
+        Select {
+            distinct: Some(Distinct::Rows),
+            ..self
+        }
+    }
+
+    /// Keeps only the first row of each set of rows where `terms` are equal.
+    ///
+    /// Only supported by Postgres, rendering it for any other dialect panics.
+    pub const fn distinct_on<D>(
+        self,
+        terms: D,
+    ) -> Select<Source, Proj, Filter, Order, Group, Having, D, Windows>
+    where
+        Self: ~const Destruct,
+    {
+        Select {
+            distinct: Some(Distinct::On(terms)),
+            ..self
+        }
+    }
+
+    /// Adds a predicate to the `WHERE` clause.
+    ///
+    /// Calling this more than once `AND`s the predicates together, in the order
+    /// they were added.
+    pub const fn filter<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Select<Source, Proj, Conjunction<Filter, Pred>, Order, Group, Having, DistinctOn, Windows>
+    {
+        Select {
+            filter: Some(Conjunction {
+                prev: self.filter,
+                predicate,
+            }),
+            ..self
+        }
+    }
+
+    /// Alias of [`Select::filter`].
+    pub const fn where_<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Select<Source, Proj, Conjunction<Filter, Pred>, Order, Group, Having, DistinctOn, Windows>
+    {
+        self.filter(predicate)
+    }
+
+    /// Sets the `GROUP BY` clause, replacing any previous one.
+    ///
+    /// `terms` is a single expression or a tuple of them.
+    pub const fn group_by<G>(
+        self,
+        terms: G,
+    ) -> Select<Source, Proj, Filter, Order, G, Having, DistinctOn, Windows>
+    where
+        Self: ~const Destruct,
+    {
+        Select {
+            group: Some(terms),
+            ..self
+        }
+    }
+
+    /// Adds a predicate to the `HAVING` clause.
+    ///
+    /// Calling this more than once `AND`s the predicates together, in the order
+    /// they were added.
+    pub const fn having<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Select<Source, Proj, Filter, Order, Group, Conjunction<Having, Pred>, DistinctOn, Windows>
+    {
+        Select {
+            having: Some(Conjunction {
+                prev: self.having,
+                predicate,
+            }),
+            ..self
+        }
+    }
+
+    /// Defines a window named `name` in the `WINDOW` clause, to be referred
+    /// to by a [`WindowName`](crate::expression::window::WindowName).
+    pub const fn window<W>(
+        self,
+        name: &'static str,
+        window: W,
+    ) -> Select<Source, Proj, Filter, Order, Group, Having, DistinctOn, WindowDefinitions<Windows, W>>
+    {
+        Select {
+            windows: Some(WindowDefinitions::new(self.windows, name, window)),
+            ..self
+        }
+    }
+
+    /// Sets the `ORDER BY` clause, replacing any previous one.
+    ///
+    /// `terms` is a single ordering term or a tuple of them, each either an
+    /// expression or an [`Ordering`](crate::expression::order::Ordering) built
+    /// with `.asc()` or `.desc()`.
+    pub const fn order_by<O>(
+        self,
+        terms: O,
+    ) -> Select<Source, Proj, Filter, O, Group, Having, DistinctOn, Windows>
+    where
+        Self: ~const Destruct,
+    {
+        Select {
+            order: Some(terms),
+            ..self
         }
     }
 
     impl_join!(inner_join, Inner);
     impl_join!(left_join, Left);
+    impl_join!(right_join, Right);
+    impl_join!(full_join, Full);
     impl_join!(cross_join, Cross);
+    impl_join!(cross_join_lateral, CrossLateral, unconditional);
+    impl_join!(left_join_lateral, LeftLateral);
+
+    impl_set_operations!();
+
+    /// Uses this query as a derived table named `alias`, as in
+    /// `(SELECT ...) AS alias`.
+    pub const fn alias(self, alias: &'static str) -> DerivedTable<Self> {
+        DerivedTable { query: self, alias }
+    }
+
+    /// Uses this query as an expression, as in `(SELECT ...)`.
+    ///
+    /// The query must return a single column, and at most one row.
+    pub const fn subquery(self) -> Subquery<Self> {
+        Subquery { query: self }
+    }
 
     pub const fn limit(mut self, limit: u64) -> Self {
         self.limit = Some(limit);
@@ -84,17 +356,25 @@ impl<Source, Proj> Select<Source, Proj> {
     }
 }
 
-#[derive(PartialEq, Eq)]
+pub enum Distinct<On> {
+    Rows,
+    On(On),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum JoinStyle {
     Inner,
     Left,
+    Right,
+    Full,
     Cross,
+    CrossLateral,
+    LeftLateral,
 }
 
-pub struct Join<Lhs, Rhs, On> {
+pub struct Join<Lhs, Rhs, On, const STYLE: JoinStyle> {
     left: Lhs,
     right: Rhs,
-    style: JoinStyle,
     on: JoinOn<On>,
 }
 
@@ -102,67 +382,662 @@ pub enum JoinOn<On> {
     Explicit(On),
     Using(On),
     Natural,
+    /// `ON TRUE`, or nothing at all for cross joins.
+    Unconditional,
 }
 
-impl<Lhs, Rhs, On> const SqlExpression for Join<Lhs, Rhs, On>
+impl<Lhs, Rhs, On, const STYLE: JoinStyle> const SqlExpression for Join<Lhs, Rhs, On, STYLE>
 where
     Lhs: ~const SqlExpression,
     Rhs: ~const SqlExpression,
     On: ~const SqlExpression,
 {
+    const NULLABLE: bool = match STYLE {
+        JoinStyle::Inner | JoinStyle::Cross | JoinStyle::CrossLateral => {
+            Lhs::NULLABLE || Rhs::NULLABLE
+        }
+        JoinStyle::Left | JoinStyle::Right | JoinStyle::Full | JoinStyle::LeftLateral => true,
+    };
+
     fn write_sql_expression(&self, sql: &mut Sql) {
         self.left.write_sql_expression(sql);
 
+        let dialect = sql.dialect();
+
+        match (dialect, STYLE) {
+            (Dialect::MySql, JoinStyle::Full) => {
+                panic!("FULL OUTER JOIN is not supported by MySQL");
+            }
+            (Dialect::Sqlite, JoinStyle::CrossLateral | JoinStyle::LeftLateral) => {
+                panic!("LATERAL joins are not supported by SQLite");
+            }
+            (Dialect::SqlServer, JoinStyle::LeftLateral) => {
+                if !matches!(self.on, JoinOn::Unconditional) {
+                    panic!("SQL Server lateral joins can't have a join condition");
+                }
+            }
+            _ => {}
+        }
+
         if matches!(self.on, JoinOn::Natural) {
             sql.push_str(" NATURAL");
         }
 
-        match self.style {
-            JoinStyle::Inner => sql.push_str(" INNER JOIN "),
-            JoinStyle::Left => sql.push_str(" LEFT OUTER JOIN "),
-            JoinStyle::Cross => sql.push_str(" CROSS JOIN "),
+        match (STYLE, dialect) {
+            (JoinStyle::Inner, _) => sql.push_str(" INNER JOIN "),
+            (JoinStyle::Left, _) => sql.push_str(" LEFT OUTER JOIN "),
+            (JoinStyle::Right, _) => sql.push_str(" RIGHT OUTER JOIN "),
+            (JoinStyle::Full, _) => sql.push_str(" FULL OUTER JOIN "),
+            (JoinStyle::Cross, _) => sql.push_str(" CROSS JOIN "),
+            (JoinStyle::CrossLateral, Dialect::SqlServer) => sql.push_str(" CROSS APPLY "),
+            (JoinStyle::CrossLateral, _) => sql.push_str(" CROSS JOIN LATERAL "),
+            (JoinStyle::LeftLateral, Dialect::SqlServer) => sql.push_str(" OUTER APPLY "),
+            (JoinStyle::LeftLateral, _) => sql.push_str(" LEFT OUTER JOIN LATERAL "),
         };
 
         self.right.write_sql_expression(sql);
 
         match &self.on {
-            JoinOn::Explicit(on) => on.write_sql_expression(sql.push_str(" ON ")),
+            JoinOn::Explicit(on) => on.write_sql_predicate(sql.push_str(" ON ")),
             JoinOn::Using(columns) => columns.write_sql_expression(sql.push_str(" USING ")),
-            JoinOn::Natural => {},
+            JoinOn::Natural => {}
+            JoinOn::Unconditional => match (STYLE, dialect) {
+                (JoinStyle::Cross | JoinStyle::CrossLateral, _)
+                | (JoinStyle::LeftLateral, Dialect::SqlServer) => {}
+                _ => true.write_sql_predicate(sql.push_str(" ON ")),
+            },
         }
     }
 }
 
-pub struct IncompleteSelectJoin<Lhs, Rhs, Proj> {
-    select: Select<Lhs, Proj>,
+/// The [`Select`] resulting from joining `Rhs` to `Lhs` on `On`.
+type JoinedSelect<
+    Lhs,
+    Rhs,
+    On,
+    const STYLE: JoinStyle,
+    Proj,
+    Filter,
+    Order,
+    Group,
+    Having,
+    DistinctOn,
+    Windows,
+> = Select<Join<Lhs, Rhs, On, STYLE>, Proj, Filter, Order, Group, Having, DistinctOn, Windows>;
+
+pub struct IncompleteSelectJoin<
+    Lhs,
+    Rhs,
+    const STYLE: JoinStyle,
+    Proj,
+    Filter,
+    Order,
+    Group,
+    Having,
+    DistinctOn,
+    Windows,
+> {
+    select: Select<Lhs, Proj, Filter, Order, Group, Having, DistinctOn, Windows>,
     right: Rhs,
-    style: JoinStyle,
 }
 
-impl<Lhs, Rhs, Proj> IncompleteSelectJoin<Lhs, Rhs, Proj> {
-    pub fn on<On>(self, on: On) -> Select<Join<Lhs, Rhs, On>, Proj> {
+impl<Lhs, Rhs, const STYLE: JoinStyle, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    IncompleteSelectJoin<Lhs, Rhs, STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+{
+    pub const fn on<On>(
+        self,
+        on: On,
+    ) -> JoinedSelect<Lhs, Rhs, On, STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    {
         self.construct(JoinOn::Explicit(on))
     }
 
-    pub fn using<On>(self, columns: On) -> Select<Join<Lhs, Rhs, On>, Proj> {
+    pub const fn using<On>(
+        self,
+        columns: On,
+    ) -> JoinedSelect<Lhs, Rhs, On, STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    {
         self.construct(JoinOn::Using(columns))
     }
 
-    pub fn natural(self) -> Select<Join<Lhs, Rhs, ()>, Proj> {
+    pub const fn natural(
+        self,
+    ) -> JoinedSelect<Lhs, Rhs, (), STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    {
         self.construct(JoinOn::Natural)
     }
 
-    fn construct<On>(self, join_on: JoinOn<On>) -> Select<Join<Lhs, Rhs, On>, Proj> {
+    /// Joins every pair of rows, as in `LEFT JOIN LATERAL ... ON TRUE`.
+    pub const fn on_true(
+        self,
+    ) -> JoinedSelect<Lhs, Rhs, (), STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    {
+        self.construct(JoinOn::Unconditional)
+    }
+
+    const fn construct<On>(
+        self,
+        join_on: JoinOn<On>,
+    ) -> JoinedSelect<Lhs, Rhs, On, STYLE, Proj, Filter, Order, Group, Having, DistinctOn, Windows>
+    {
         Select {
             from: Join {
                 left: self.select.from,
                 right: self.right,
-                style: self.style,
                 on: join_on,
             },
-            projections: self.select.projections,
-            offset: self.select.offset,
-            limit: self.select.limit
+            ..self.select
+        }
+    }
+}
+
+/// A subquery used as a table, see [`Select::alias`].
+pub struct DerivedTable<Query> {
+    query: Query,
+    alias: &'static str,
+}
+
+impl<Query> DerivedTable<Query> {
+    /// The column `name` of the derived table, qualified by its alias.
+    ///
+    /// It's nullable when any of the columns of the query is, as it can't be
+    /// told apart from the others by name at compile time.
+    pub const fn column(&self, name: &'static str) -> DerivedColumn<Query> {
+        DerivedColumn {
+            table: self.alias,
+            name,
+            _query: PhantomData,
+        }
+    }
+}
+
+impl<Query> const SqlExpression for DerivedTable<Query>
+where
+    Query: ~const SqlExpression,
+{
+    const NULLABLE: bool = Query::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push(b'(');
+        self.query.write_sql_expression(sql);
+        sql.push_str(") AS ").push_ident(self.alias);
+    }
+}
+
+/// A query used as a scalar expression, see [`Select::subquery`].
+pub struct Subquery<Query> {
+    query: Query,
+}
+
+impl<Query> const SqlExpression for Subquery<Query>
+where
+    Query: ~const SqlExpression,
+{
+    const CLASS: SqlTypeClass = Query::CLASS;
+    const NULLABLE: bool = Query::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push(b'(');
+        self.query.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// A column of a [`DerivedTable`], rendered as `alias.name`, nullable when
+/// the columns of `Query` are.
+pub struct DerivedColumn<Query = ()> {
+    table: &'static str,
+    name: &'static str,
+    _query: PhantomData<fn() -> Query>,
+}
+
+impl DerivedColumn {
+    pub const fn new(table: &'static str, name: &'static str) -> Self {
+        Self {
+            table,
+            name,
+            _query: PhantomData,
         }
     }
 }
+
+impl<Query> Clone for DerivedColumn<Query> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<Query> Copy for DerivedColumn<Query> {}
+
+impl<Query> const SqlExpression for DerivedColumn<Query>
+where
+    Query: ~const SqlExpression,
+{
+    const NULLABLE: bool = Query::NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_ident(self.table).dot().push_ident(self.name);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{
+            aggregate::{count, count_all, max, min},
+            ops::{all, any, exists, not_exists, SqlExpressionExt},
+            param::Param,
+            window::{row_number, window, FrameBound, WindowName},
+        },
+        query::update::update,
+        schema::fixtures::*,
+    };
+
+    const fn raw(part: &str) -> Sql {
+        let mut sql = Sql::default();
+        sql.push_str(part);
+        sql
+    }
+
+    #[test]
+    fn test_filter() {
+        static SQL: &str = from(USERS).filter(raw("users.id = $1")).to_sql().into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id,users.name,users.email FROM public.users WHERE users.id = $1"
+        );
+    }
+
+    #[test]
+    fn test_filter_chain_and_position() {
+        static SQL: &str = from(USERS)
+            .inner_join(POSTS)
+            .on(USERS_ID.eq(POSTS_USER_ID))
+            .select((USERS_NAME, POSTS_TITLE))
+            .filter(raw("users.id = $1"))
+            .where_(POSTS_TITLE.is_not_null().or(USERS_NAME.is_null()))
+            .offset(2)
+            .limit(5)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.name,posts.title FROM public.users INNER JOIN public.posts \
+             ON users.id = posts.user_id WHERE users.id = $1 AND (posts.title IS NOT NULL OR users.name IS NULL) \
+             LIMIT 5 OFFSET 2"
+        );
+    }
+
+    #[test]
+    fn test_order_by() {
+        static SQL: &str = from(USERS)
+            .select((USERS_ID, USERS_NAME))
+            .filter(USERS_EMAIL.is_not_null())
+            .order_by((
+                USERS_NAME.desc().nulls_last(),
+                USERS_EMAIL.asc().nulls_first(),
+                USERS_ID,
+            ))
+            .offset(5)
+            .limit(5)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id,users.name FROM public.users WHERE users.email IS NOT NULL \
+             ORDER BY users.name DESC NULLS LAST,users.email ASC NULLS FIRST,users.id \
+             LIMIT 5 OFFSET 5"
+        );
+    }
+
+    #[test]
+    fn test_group_by_having() {
+        static SQL: &str = from(USERS)
+            .inner_join(POSTS)
+            .on(USERS_ID.eq(POSTS_USER_ID))
+            .select((USERS_ID, count_all(), max(POSTS_TITLE)))
+            .group_by(USERS_ID)
+            .having(count(POSTS_ID).gt(Param::<i64>::new()))
+            .having(min(POSTS_TITLE).is_not_null())
+            .order_by(count_all().desc())
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT users.id,COUNT(*),MAX(posts.title) FROM public.users \
+             INNER JOIN public.posts ON users.id = posts.user_id GROUP BY users.id \
+             HAVING COUNT(posts.id) > $1 AND MIN(posts.title) IS NOT NULL \
+             ORDER BY COUNT(*) DESC"
+        );
+    }
+
+    #[test]
+    fn test_distinct() {
+        static DISTINCT: &str = from(USERS)
+            .select(USERS_NAME)
+            .distinct()
+            .to_sql()
+            .into_str();
+        static DISTINCT_ON: &str = from(POSTS)
+            .select((POSTS_USER_ID, POSTS_TITLE))
+            .distinct_on(POSTS_USER_ID)
+            .order_by((POSTS_USER_ID, POSTS_ID.desc()))
+            .to_sql_with(Dialect::Postgres)
+            .into_str();
+
+        assert_eq!(DISTINCT, "SELECT DISTINCT users.name FROM public.users");
+        assert_eq!(
+            DISTINCT_ON,
+            "SELECT DISTINCT ON (posts.user_id) posts.user_id,posts.title FROM public.posts \
+             ORDER BY posts.user_id,posts.id DESC"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "DISTINCT ON is only supported by Postgres")]
+    fn test_distinct_on_requires_postgres() {
+        from(POSTS)
+            .distinct_on(POSTS_USER_ID)
+            .to_sql_with(Dialect::MySql);
+    }
+
+    #[test]
+    fn test_self_join() {
+        static EMPLOYEE: AliasedTable = EMPLOYEES.alias("e");
+        static MANAGER: AliasedTable = EMPLOYEES.alias("m");
+
+        static ALL: &str = from_aliased(EMPLOYEE).to_sql().into_str();
+        static SQL: &str = from_aliased(EMPLOYEE)
+            .left_join(MANAGER)
+            .on(EMPLOYEE
+                .column(EMPLOYEES_MANAGER_ID)
+                .eq(MANAGER.column(EMPLOYEES_ID)))
+            .select((
+                EMPLOYEE.column(EMPLOYEES_NAME).as_("employee"),
+                MANAGER.column(EMPLOYEES_NAME).as_("manager"),
+            ))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            ALL,
+            "SELECT e.id,e.name,e.manager_id FROM hr.employees AS e"
+        );
+        assert_eq!(
+            SQL,
+            "SELECT e.name AS employee,m.name AS manager FROM hr.employees AS e \
+             LEFT OUTER JOIN hr.employees AS m ON e.manager_id = m.id"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "the column doesn't belong to the aliased table")]
+    fn test_aliased_column_of_another_table() {
+        USERS.alias("u").column(POSTS_ID);
+    }
+
+    #[test]
+    fn test_outer_joins() {
+        static RIGHT: &str = from(USERS)
+            .right_join(POSTS)
+            .on(USERS_ID.eq(POSTS_USER_ID))
+            .select((USERS_NAME, POSTS_TITLE))
+            .to_sql()
+            .into_str();
+        static FULL: &str = from(USERS)
+            .full_join(POSTS)
+            .on(USERS_ID.eq(POSTS_USER_ID))
+            .select(USERS_NAME)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            RIGHT,
+            "SELECT users.name,posts.title FROM public.users \
+             RIGHT OUTER JOIN public.posts ON users.id = posts.user_id"
+        );
+        assert_eq!(
+            FULL,
+            "SELECT users.name FROM public.users \
+             FULL OUTER JOIN public.posts ON users.id = posts.user_id"
+        );
+    }
+
+    #[test]
+    fn test_lateral_joins() {
+        static CROSS: &str = from(USERS)
+            .cross_join_lateral(
+                from(POSTS)
+                    .select(POSTS_TITLE)
+                    .filter(POSTS_USER_ID.eq(USERS_ID))
+                    .order_by(POSTS_ID.desc())
+                    .limit(1)
+                    .alias("latest"),
+            )
+            .select((USERS_NAME, DerivedColumn::new("latest", "title")))
+            .to_sql()
+            .into_str();
+        static LEFT: &str = from(USERS)
+            .left_join_lateral(
+                from(POSTS)
+                    .select(count_all().as_("total"))
+                    .filter(POSTS_USER_ID.eq(USERS_ID))
+                    .alias("counts"),
+            )
+            .on_true()
+            .select(USERS_NAME)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            CROSS,
+            "SELECT users.name,latest.title FROM public.users CROSS JOIN LATERAL \
+             (SELECT posts.title FROM public.posts WHERE posts.user_id = users.id \
+             ORDER BY posts.id DESC LIMIT 1) AS latest"
+        );
+        assert_eq!(
+            LEFT,
+            "SELECT users.name FROM public.users LEFT OUTER JOIN LATERAL \
+             (SELECT COUNT(*) AS total FROM public.posts WHERE posts.user_id = users.id) \
+             AS counts ON TRUE"
+        );
+    }
+
+    #[test]
+    fn test_subquery_source() {
+        static SQL: &str = from_subquery(
+            from(POSTS)
+                .select((POSTS_USER_ID, count_all().as_("total")))
+                .filter(POSTS_TITLE.like(Param::<String>::new()))
+                .group_by(POSTS_USER_ID)
+                .alias("counts"),
+        )
+        .filter(DerivedColumn::new("counts", "total").gt(Param::<i64>::new()))
+        .to_sql()
+        .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT * FROM (SELECT posts.user_id,COUNT(*) AS total FROM public.posts \
+             WHERE posts.title LIKE $1 GROUP BY posts.user_id) AS counts WHERE counts.total > $2"
+        );
+    }
+
+    #[test]
+    fn test_subquery_expressions() {
+        static SQL: (u32, &str) = {
+            let sql = from(USERS)
+                .select((
+                    USERS_NAME,
+                    from(POSTS)
+                        .select(count_all())
+                        .filter(POSTS_USER_ID.eq(USERS_ID))
+                        .subquery()
+                        .as_("posts"),
+                ))
+                .filter(USERS_ID.in_(from(POSTS).select(POSTS_USER_ID)))
+                .filter(exists(
+                    from(POSTS)
+                        .select(POSTS_ID)
+                        .filter(POSTS_TITLE.eq(Param::<String>::new())),
+                ))
+                .filter(not_exists(
+                    from(EMPLOYEES).filter(EMPLOYEES_NAME.eq(USERS_NAME)),
+                ))
+                .filter(USERS_ID.ne(all(from(POSTS).select(POSTS_USER_ID))))
+                .filter(
+                    USERS_ID.eq(any(from(POSTS)
+                        .select(POSTS_USER_ID)
+                        .filter(POSTS_ID.lt(Param::<i64>::new())))),
+                )
+                .to_sql();
+
+            (sql.bindings(), sql.into_str())
+        };
+
+        assert_eq!(SQL.0, 2);
+        assert_eq!(
+            SQL.1,
+            "SELECT users.name,(SELECT COUNT(*) FROM public.posts \
+             WHERE posts.user_id = users.id) AS posts FROM public.users \
+             WHERE users.id IN (SELECT posts.user_id FROM public.posts) \
+             AND EXISTS (SELECT posts.id FROM public.posts WHERE posts.title = $1) \
+             AND NOT EXISTS (SELECT employees.id,employees.name,employees.manager_id \
+             FROM hr.employees WHERE employees.name = users.name) \
+             AND users.id <> ALL (SELECT posts.user_id FROM public.posts) \
+             AND users.id = ANY (SELECT posts.user_id FROM public.posts WHERE posts.id < $2)"
+        );
+    }
+
+    #[test]
+    fn test_named_windows() {
+        static SQL: &str = from(POSTS)
+            .select((
+                POSTS_ID,
+                row_number().over(WindowName("by_user")),
+                count_all().over(WindowName("running")),
+            ))
+            .window("by_user", window().partition_by(POSTS_USER_ID))
+            .window(
+                "running",
+                window()
+                    .order_by(POSTS_ID)
+                    .rows(FrameBound::UnboundedPreceding, FrameBound::CurrentRow),
+            )
+            .order_by(POSTS_ID)
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "SELECT posts.id,ROW_NUMBER() OVER by_user,COUNT(*) OVER running \
+             FROM public.posts WINDOW by_user AS (PARTITION BY posts.user_id),\
+             running AS (ORDER BY posts.id ROWS BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW) \
+             ORDER BY posts.id"
+        );
+    }
+
+    #[test]
+    fn test_dialects() {
+        static SQL: [&str; 5] = {
+            let query = from(USERS)
+                .left_join_lateral(from(POSTS).filter(POSTS_USER_ID.eq(USERS_ID)).alias("p"))
+                .on_true()
+                .filter(USERS_NAME.ilike(Param::<String>::new()))
+                .order_by(USERS_ID)
+                .offset(20)
+                .limit(10);
+
+            [
+                query.to_sql_with(Dialect::Postgres).into_str(),
+                query.to_sql_with(Dialect::MySql).into_str(),
+                query.to_sql_with(Dialect::SqlServer).into_str(),
+                query.to_sql_with(Dialect::Ansi).into_str(),
+                from(USERS)
+                    .filter(USERS_NAME.ilike(Param::<String>::new()))
+                    .offset(20)
+                    .to_sql_with(Dialect::Sqlite)
+                    .into_str(),
+            ]
+        };
+
+        assert_eq!(
+            SQL,
+            [
+                "SELECT users.id,users.name,users.email FROM public.users \
+                 LEFT OUTER JOIN LATERAL (SELECT posts.id,posts.user_id,posts.title \
+                 FROM public.posts WHERE posts.user_id = users.id) AS p ON TRUE \
+                 WHERE users.name ILIKE $1 ORDER BY users.id LIMIT 10 OFFSET 20",
+                "SELECT users.id,users.name,users.email FROM public.users \
+                 LEFT OUTER JOIN LATERAL (SELECT posts.id,posts.user_id,posts.title \
+                 FROM public.posts WHERE posts.user_id = users.id) AS p ON TRUE \
+                 WHERE LOWER(users.name) LIKE LOWER(?) ORDER BY users.id LIMIT 10 OFFSET 20",
+                "SELECT users.id,users.name,users.email FROM [public].users \
+                 OUTER APPLY (SELECT posts.id,posts.user_id,posts.title \
+                 FROM [public].posts WHERE posts.user_id = users.id) AS p \
+                 WHERE LOWER(users.name) LIKE LOWER(@p1) ORDER BY users.id \
+                 OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY",
+                "SELECT users.id,users.name,users.email FROM public.users \
+                 LEFT OUTER JOIN LATERAL (SELECT posts.id,posts.user_id,posts.title \
+                 FROM public.posts WHERE posts.user_id = users.id) AS p ON TRUE \
+                 WHERE LOWER(users.name) LIKE LOWER(?) ORDER BY users.id \
+                 OFFSET 20 ROWS FETCH NEXT 10 ROWS ONLY",
+                "SELECT users.id,users.name,users.email FROM public.users \
+                 WHERE LOWER(users.name) LIKE LOWER(?) LIMIT -1 OFFSET 20",
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "SQL Server requires ORDER BY to use OFFSET and FETCH")]
+    fn test_sql_server_limit_requires_order_by() {
+        from(USERS).limit(10).to_sql_with(Dialect::SqlServer);
+    }
+
+    #[test]
+    fn test_outer_join_nullability() {
+        static SQL: &str = update(EMPLOYEES)
+            .set(
+                EMPLOYEES_NAME,
+                from(USERS)
+                    .inner_join(POSTS)
+                    .on(POSTS_USER_ID.eq(USERS_ID))
+                    .select(USERS_NAME)
+                    .limit(1)
+                    .subquery(),
+            )
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "UPDATE hr.employees SET name = (SELECT users.name FROM public.users \
+             INNER JOIN public.posts ON posts.user_id = users.id LIMIT 1)"
+        );
+
+        const fn nullable<E: SqlExpression>(_: &E) -> bool {
+            E::NULLABLE
+        }
+
+        assert!(nullable(
+            &from(USERS)
+                .left_join(POSTS)
+                .on(POSTS_USER_ID.eq(USERS_ID))
+                .select(POSTS_USER_ID)
+        ));
+        assert!(nullable(
+            &from(USERS)
+                .left_join(POSTS)
+                .on(POSTS_USER_ID.eq(USERS_ID))
+                .select(POSTS_ID.as_("post_id"))
+                .alias("p")
+                .column("post_id")
+        ));
+        assert!(!nullable(&from(USERS).select(USERS_ID).subquery()));
+    }
+}
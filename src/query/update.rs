@@ -0,0 +1,257 @@
+use std::marker::Destruct;
+
+use crate::{
+    dialect::Dialect,
+    expression::{check_assignable, check_nullable, Sql, SqlExpression},
+    query::{write_returning, Conjunction},
+    schema::{Column, ColumnNames, SqlTypeClass, Table},
+};
+
+pub const fn update(table: Table) -> UpdateTable {
+    UpdateTable { table }
+}
+
+/// An `UPDATE` statement still missing its first assignment.
+pub struct UpdateTable {
+    table: Table,
+}
+
+impl UpdateTable {
+    pub const fn set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> Update<Assignments<(), Value>>
+    where
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        Update {
+            table: self.table,
+            assignments: Assignments::new(None, column, value),
+            from: None,
+            filter: None,
+            returning: None,
+        }
+    }
+}
+
+pub struct Update<Assign, From = (), Filter = (), Returning = ()> {
+    table: Table,
+    assignments: Assign,
+    from: Option<From>,
+    filter: Option<Filter>,
+    returning: Option<Returning>,
+}
+
+impl<Assign, From, Filter, Returning> const SqlExpression
+    for Update<Assign, From, Filter, Returning>
+where
+    Assign: ~const SqlExpression,
+    From: ~const SqlExpression,
+    Filter: ~const SqlExpression,
+    Returning: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("UPDATE ");
+        self.table.write_sql_expression(sql);
+
+        sql.push_str(" SET ");
+        self.assignments.write_sql_expression(sql);
+
+        if let Some(from) = &self.from {
+            if matches!(sql.dialect(), Dialect::MySql) {
+                panic!("UPDATE ... FROM is not supported by MySQL");
+            }
+
+            from.write_sql_expression(sql.push_str(" FROM "));
+        }
+
+        if let Some(filter) = &self.filter {
+            filter.write_sql_predicate(sql.push_str(" WHERE "));
+        }
+
+        write_returning(sql, &self.returning);
+    }
+}
+
+impl<Assign, From, Filter, Returning> Update<Assign, From, Filter, Returning> {
+    /// Adds a `column = value` assignment to the `SET` clause.
+    pub const fn set<const C: SqlTypeClass, const NULLABLE: bool, Value>(
+        self,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> Update<Assignments<Assign, Value>, From, Filter, Returning>
+    where
+        Value: SqlExpression,
+        [(); check_assignable(C, Value::CLASS)]:,
+        [(); check_nullable(NULLABLE, Value::NULLABLE)]:,
+    {
+        Update {
+            assignments: Assignments::new(Some(self.assignments), column, value),
+            ..self
+        }
+    }
+
+    /// Joins the updated table with `source`, as in `UPDATE ... FROM source`.
+    ///
+    /// The join condition goes in the `WHERE` clause. MySQL has no `FROM` in
+    /// `UPDATE`, so rendering it for MySQL panics.
+    pub const fn from<F>(self, source: F) -> Update<Assign, F, Filter, Returning>
+    where
+        Self: ~const Destruct,
+    {
+        Update {
+            from: Some(source),
+            ..self
+        }
+    }
+
+    /// Adds a predicate to the `WHERE` clause.
+    ///
+    /// Calling this more than once `AND`s the predicates together, in the order
+    /// they were added.
+    pub const fn filter<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Update<Assign, From, Conjunction<Filter, Pred>, Returning> {
+        Update {
+            filter: Some(Conjunction {
+                prev: self.filter,
+                predicate,
+            }),
+            ..self
+        }
+    }
+
+    /// Alias of [`Update::filter`].
+    pub const fn where_<Pred>(
+        self,
+        predicate: Pred,
+    ) -> Update<Assign, From, Conjunction<Filter, Pred>, Returning> {
+        self.filter(predicate)
+    }
+
+    /// Returns `projections` from the updated rows, with their new values.
+    pub const fn returning<P>(self, projections: P) -> Update<Assign, From, Filter, P>
+    where
+        Self: ~const Destruct,
+    {
+        Update {
+            returning: Some(projections),
+            ..self
+        }
+    }
+}
+
+/// The `SET` clause of an [`Update`], one `column = value` per `set` call.
+pub struct Assignments<Prev, Value> {
+    prev: Option<Prev>,
+    column: Column,
+    value: Value,
+}
+
+impl<Prev, Value> Assignments<Prev, Value> {
+    pub(crate) const fn new<const C: SqlTypeClass, const NULLABLE: bool>(
+        prev: Option<Prev>,
+        column: Column<C, NULLABLE>,
+        value: Value,
+    ) -> Self {
+        Self {
+            prev,
+            column: column.untyped(),
+            value,
+        }
+    }
+}
+
+impl<Prev, Value> const SqlExpression for Assignments<Prev, Value>
+where
+    Prev: ~const SqlExpression,
+    Value: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if let Some(prev) = &self.prev {
+            prev.write_sql_expression(sql);
+            sql.comma();
+        }
+
+        self.column.write_column_names(sql);
+        sql.push_str(" = ");
+        self.value.write_sql_expression(sql);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        schema::fixtures::*,
+    };
+
+    #[test]
+    fn test_update() {
+        static SQL: &str = update(USERS)
+            .set(USERS_NAME, Param::<String>::new())
+            .set(USERS_EMAIL, Param::<String>::new())
+            .filter(USERS_ID.eq(Param::<i64>::new()))
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "UPDATE public.users SET name = $1,email = $2 WHERE users.id = $3"
+        );
+    }
+
+    #[test]
+    fn test_update_from() {
+        static SQL: &str = update(POSTS)
+            .set(POSTS_TITLE, USERS_NAME)
+            .from(USERS)
+            .filter(POSTS_USER_ID.eq(USERS_ID))
+            .where_(USERS_EMAIL.is_not_null())
+            .to_sql()
+            .into_str();
+
+        assert_eq!(
+            SQL,
+            "UPDATE public.posts SET title = users.name FROM public.users \
+             WHERE posts.user_id = users.id AND users.email IS NOT NULL"
+        );
+    }
+
+    #[test]
+    fn test_update_returning() {
+        static SQL: (u32, &str) = {
+            let sql = update(USERS)
+                .set(USERS_EMAIL, Param::<String>::new())
+                .filter(USERS_ID.eq(Param::<i64>::new()))
+                .returning((USERS_ID, USERS_EMAIL))
+                .to_sql();
+
+            (sql.bindings(), sql.into_str())
+        };
+
+        assert_eq!(
+            SQL,
+            (
+                2,
+                "UPDATE public.users SET email = $1 WHERE users.id = $2 \
+                 RETURNING users.id,users.email"
+            )
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "UPDATE ... FROM is not supported by MySQL")]
+    fn test_update_from_mysql() {
+        update(POSTS)
+            .set(POSTS_TITLE, USERS_NAME)
+            .from(USERS)
+            .filter(POSTS_USER_ID.eq(USERS_ID))
+            .to_sql_with(Dialect::MySql);
+    }
+}
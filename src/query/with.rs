@@ -0,0 +1,274 @@
+//! Common table expressions.
+//!
+//! Each CTE is declared as a [`Table`] with an empty schema, whose columns are
+//! the ones returned by its query. Its columns can then be used like any
+//! other, and the CTE itself passed to [`from`](crate::query::select::from).
+use crate::{
+    dialect::Dialect,
+    expression::{Sql, SqlExpression},
+    schema::{ColumnNames, Table},
+};
+
+/// Starts a `WITH` query defining `table` as the result of `query`.
+pub const fn with<Q>(table: Table, query: Q) -> With<Cte<(), Q>> {
+    With {
+        ctes: Cte::new(None, table, query),
+        recursive: false,
+    }
+}
+
+/// Starts a `WITH RECURSIVE` query defining `table` as
+/// `anchor UNION ALL step`.
+///
+/// `step` usually joins with `table` itself.
+pub const fn with_recursive<A, S>(
+    table: Table,
+    anchor: A,
+    step: S,
+) -> With<Cte<(), RecursiveQuery<A, S>>> {
+    With {
+        ctes: Cte::new(None, table, RecursiveQuery { anchor, step }),
+        recursive: true,
+    }
+}
+
+pub struct With<Ctes> {
+    ctes: Ctes,
+    recursive: bool,
+}
+
+impl<Ctes> With<Ctes> {
+    /// Defines another CTE, which can refer to the previous ones.
+    pub const fn with<Q>(self, table: Table, query: Q) -> With<Cte<Ctes, Q>> {
+        With {
+            ctes: Cte::new(Some(self.ctes), table, query),
+            recursive: self.recursive,
+        }
+    }
+
+    /// Defines another recursive CTE, see [`with_recursive`].
+    pub const fn with_recursive<A, S>(
+        self,
+        table: Table,
+        anchor: A,
+        step: S,
+    ) -> With<Cte<Ctes, RecursiveQuery<A, S>>> {
+        With {
+            ctes: Cte::new(Some(self.ctes), table, RecursiveQuery { anchor, step }),
+            recursive: true,
+        }
+    }
+
+    /// Runs `statement` with the defined CTEs in scope.
+    pub const fn query<Stmt>(self, statement: Stmt) -> WithQuery<Ctes, Stmt> {
+        WithQuery {
+            with: self,
+            statement,
+        }
+    }
+}
+
+impl<Prev, Q> With<Cte<Prev, Q>> {
+    /// Hints the last defined CTE to be computed once, as in
+    /// `AS MATERIALIZED (...)`.
+    pub const fn materialized(mut self) -> Self {
+        self.ctes.materialized = Some(true);
+        self
+    }
+
+    /// Hints the last defined CTE to be inlined in the queries using it, as
+    /// in `AS NOT MATERIALIZED (...)`.
+    pub const fn not_materialized(mut self) -> Self {
+        self.ctes.materialized = Some(false);
+        self
+    }
+}
+
+/// A chain of CTE definitions, one per call to [`with`].
+pub struct Cte<Prev, Q> {
+    prev: Option<Prev>,
+    table: Table,
+    query: Q,
+    materialized: Option<bool>,
+}
+
+impl<Prev, Q> Cte<Prev, Q> {
+    const fn new(prev: Option<Prev>, table: Table, query: Q) -> Self {
+        Self {
+            prev,
+            table,
+            query,
+            materialized: None,
+        }
+    }
+}
+
+impl<Prev, Q> const SqlExpression for Cte<Prev, Q>
+where
+    Prev: ~const SqlExpression,
+    Q: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        if let Some(prev) = &self.prev {
+            prev.write_sql_expression(sql);
+            sql.comma();
+        }
+
+        self.table.write_sql_expression(sql);
+
+        if !self.table.all_columns.is_empty() {
+            sql.push_str(" (");
+            self.table.all_columns.write_column_names(sql);
+            sql.push(b')');
+        }
+
+        sql.push_str(" AS ");
+
+        if let Some(materialized) = self.materialized {
+            if !matches!(sql.dialect(), Dialect::Postgres | Dialect::Sqlite) {
+                panic!("MATERIALIZED hints are only supported by Postgres and SQLite");
+            }
+
+            if !materialized {
+                sql.push_str("NOT ");
+            }
+
+            sql.push_str("MATERIALIZED ");
+        }
+
+        sql.push(b'(');
+        self.query.write_sql_expression(sql);
+        sql.push(b')');
+    }
+}
+
+/// The body of a recursive CTE, `anchor UNION ALL step`.
+pub struct RecursiveQuery<A, S> {
+    anchor: A,
+    step: S,
+}
+
+impl<A, S> const SqlExpression for RecursiveQuery<A, S>
+where
+    A: ~const SqlExpression,
+    S: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.anchor.write_sql_expression(sql);
+        sql.push_str(" UNION ALL ");
+        self.step.write_sql_expression(sql);
+    }
+}
+
+pub struct WithQuery<Ctes, Stmt> {
+    with: With<Ctes>,
+    statement: Stmt,
+}
+
+impl<Ctes, Stmt> const SqlExpression for WithQuery<Ctes, Stmt>
+where
+    Ctes: ~const SqlExpression,
+    Stmt: ~const SqlExpression,
+{
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_str("WITH ");
+
+        if self.with.recursive {
+            sql.push_str("RECURSIVE ");
+        }
+
+        self.with.ctes.write_sql_expression(sql);
+        self.statement.write_sql_expression(sql.spacing());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        query::select::from,
+        schema::{fixtures::*, Column, Ident, SqlType},
+    };
+
+    static CHAIN: Table = Table {
+        ident: Ident {
+            name: "chain",
+            schema: "",
+        },
+        all_columns: &[CHAIN_ID, CHAIN_MANAGER_ID],
+    };
+    static CHAIN_ID: Column = Column::new("id", &CHAIN, SqlType::BigInt);
+    static CHAIN_MANAGER_ID: Column = Column::new("manager_id", &CHAIN, SqlType::BigInt);
+
+    static AUTHORS: Table = Table {
+        ident: Ident {
+            name: "authors",
+            schema: "",
+        },
+        all_columns: &[AUTHORS_USER_ID],
+    };
+    static AUTHORS_USER_ID: Column = Column::new("user_id", &AUTHORS, SqlType::BigInt);
+
+    #[test]
+    fn test_with() {
+        static SQL: &str = with(
+            AUTHORS,
+            from(POSTS)
+                .select(POSTS_USER_ID)
+                .filter(POSTS_TITLE.is_not_null()),
+        )
+        .materialized()
+        .with(
+            CHAIN,
+            from(EMPLOYEES).select((EMPLOYEES_ID, EMPLOYEES_MANAGER_ID)),
+        )
+        .not_materialized()
+        .query(from(CHAIN).filter(CHAIN_ID.in_(from(AUTHORS))))
+        .to_sql()
+        .into_str();
+
+        assert_eq!(
+            SQL,
+            "WITH authors (user_id) AS MATERIALIZED (SELECT posts.user_id FROM public.posts \
+             WHERE posts.title IS NOT NULL),chain (id,manager_id) AS NOT MATERIALIZED \
+             (SELECT employees.id,employees.manager_id FROM hr.employees) \
+             SELECT chain.id,chain.manager_id FROM chain \
+             WHERE chain.id IN (SELECT authors.user_id FROM authors)"
+        );
+    }
+
+    #[test]
+    fn test_with_recursive() {
+        static SQL: &str = with_recursive(
+            CHAIN,
+            from(EMPLOYEES)
+                .select((EMPLOYEES_ID, EMPLOYEES_MANAGER_ID))
+                .filter(EMPLOYEES_ID.eq(Param::<i64>::new())),
+            from(EMPLOYEES)
+                .inner_join(CHAIN)
+                .on(EMPLOYEES_ID.eq(CHAIN_MANAGER_ID))
+                .select((EMPLOYEES_ID, EMPLOYEES_MANAGER_ID)),
+        )
+        .query(from(CHAIN).select(CHAIN_ID))
+        .to_sql()
+        .into_str();
+
+        assert_eq!(
+            SQL,
+            "WITH RECURSIVE chain (id,manager_id) AS (SELECT employees.id,employees.manager_id \
+             FROM hr.employees WHERE employees.id = $1 UNION ALL \
+             SELECT employees.id,employees.manager_id FROM hr.employees \
+             INNER JOIN chain ON employees.id = chain.manager_id) SELECT chain.id FROM chain"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "MATERIALIZED hints are only supported by Postgres and SQLite")]
+    fn test_materialized_requires_dialect_support() {
+        with(CHAIN, from(EMPLOYEES))
+            .materialized()
+            .query(from(CHAIN))
+            .to_sql_with(Dialect::MySql);
+    }
+}
@@ -1,4 +1,7 @@
-use crate::expression::{Sql, SqlExpression};
+use crate::{
+    dialect::Dialect,
+    expression::{add_arities, literal::Null, single_class, str_eq, Sql, SqlExpression},
+};
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub struct Ident {
@@ -8,24 +11,457 @@ pub struct Ident {
 
 impl const SqlExpression for Ident {
     fn write_sql_expression(&self, sql: &mut Sql) {
-        sql.push_str(self.schema).dot().push_str(self.name);
+        if !self.schema.is_empty() {
+            sql.push_ident(self.schema).dot();
+        }
+
+        sql.push_ident(self.name);
     }
 }
 
+/// A column and its metadata, as declared in `CREATE TABLE`.
+///
+/// The class of its type and its nullability are part of the Rust type, so
+/// comparisons and assignments are type checked at compile time. They're
+/// [`SqlTypeClass::Unknown`] and nullable for columns that aren't, as those
+/// listed in [`Table::all_columns`].
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub struct Column {
+pub struct Column<const C: SqlTypeClass = { SqlTypeClass::Unknown }, const NULLABLE: bool = true> {
     pub name: &'static str,
     pub table: &'static Table,
+    pub ty: SqlType,
+    pub nullable: bool,
+    pub default: Option<ColumnDefault>,
+    pub generated: Option<Generated>,
+}
+
+impl<const C: SqlTypeClass> Column<C> {
+    /// A nullable column of type `ty`, without default value.
+    ///
+    /// Panics if `ty` isn't of class `C`.
+    pub const fn new(name: &'static str, table: &'static Table, ty: SqlType) -> Self {
+        if !matches!(C, SqlTypeClass::Unknown) && C as u8 != ty.class() as u8 {
+            panic!("the column type doesn't belong to the SQL type class of the column");
+        }
+
+        Self {
+            name,
+            table,
+            ty,
+            nullable: true,
+            default: None,
+            generated: None,
+        }
+    }
+
+    pub const fn not_null(self) -> Column<C, false> {
+        Column {
+            name: self.name,
+            table: self.table,
+            ty: self.ty,
+            nullable: false,
+            default: self.default,
+            generated: self.generated,
+        }
+    }
+}
+
+impl<const C: SqlTypeClass, const NULLABLE: bool> Column<C, NULLABLE> {
+    pub const fn default(mut self, default: ColumnDefault) -> Self {
+        self.default = Some(default);
+        self
+    }
+
+    pub const fn generated(mut self, generated: Generated) -> Self {
+        self.generated = Some(generated);
+        self
+    }
+
+    /// The column by its bare name, without the table qualifier.
+    pub const fn unqualified(self) -> Unqualified<Column<C, NULLABLE>> {
+        Unqualified(self)
+    }
+
+    /// The same column, no longer type checked at compile time.
+    pub const fn untyped(self) -> Column {
+        Column {
+            name: self.name,
+            table: self.table,
+            ty: self.ty,
+            nullable: self.nullable,
+            default: self.default,
+            generated: self.generated,
+        }
+    }
 }
 
-impl const SqlExpression for Column {
+impl<const C: SqlTypeClass, const NULLABLE: bool> const SqlExpression for Column<C, NULLABLE> {
+    const CLASS: SqlTypeClass = C;
+    const NULLABLE: bool = NULLABLE;
+
     fn write_sql_expression(&self, sql: &mut Sql) {
-        sql.push_str(self.table.ident.name)
+        sql.push_ident(self.table.ident.name)
             .dot()
-            .push_str(self.name);
+            .push_ident(self.name);
+    }
+}
+
+/// The SQL type of a [`Column`].
+///
+/// Types are named after their Postgres spelling, each dialect renders its
+/// closest equivalent.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlType {
+    SmallInt,
+    Integer,
+    BigInt,
+    Real,
+    Double,
+    /// `NUMERIC(precision, scale)`.
+    Numeric(u32, u32),
+    Boolean,
+    Text,
+    Varchar(u32),
+    Char(u32),
+    Date,
+    Time,
+    Timestamp,
+    TimestampTz,
+    Uuid,
+    Json,
+    Jsonb,
+    Bytea,
+    /// An array of the given element type.
+    Array(&'static SqlType),
+    /// A user defined enumeration, by its type name.
+    Enum(&'static str),
+}
+
+impl SqlType {
+    pub const fn is_integer(&self) -> bool {
+        matches!(self, SqlType::SmallInt | SqlType::Integer | SqlType::BigInt)
+    }
+
+    pub const fn is_numeric(&self) -> bool {
+        self.is_integer() || matches!(self, SqlType::Real | SqlType::Double | SqlType::Numeric(..))
+    }
+
+    pub const fn is_textual(&self) -> bool {
+        matches!(
+            self,
+            SqlType::Text | SqlType::Varchar(_) | SqlType::Char(_) | SqlType::Enum(_)
+        )
+    }
+
+    pub const fn is_temporal(&self) -> bool {
+        matches!(
+            self,
+            SqlType::Date | SqlType::Time | SqlType::Timestamp | SqlType::TimestampTz
+        )
+    }
+
+    pub const fn class(&self) -> SqlTypeClass {
+        match self {
+            SqlType::SmallInt
+            | SqlType::Integer
+            | SqlType::BigInt
+            | SqlType::Real
+            | SqlType::Double
+            | SqlType::Numeric(..) => SqlTypeClass::Numeric,
+            SqlType::Text | SqlType::Varchar(_) | SqlType::Char(_) | SqlType::Enum(_) => {
+                SqlTypeClass::Textual
+            }
+            SqlType::Date | SqlType::Time | SqlType::Timestamp | SqlType::TimestampTz => {
+                SqlTypeClass::Temporal
+            }
+            SqlType::Boolean => SqlTypeClass::Boolean,
+            SqlType::Uuid => SqlTypeClass::Uuid,
+            SqlType::Json | SqlType::Jsonb => SqlTypeClass::Json,
+            SqlType::Bytea => SqlTypeClass::Binary,
+            SqlType::Array(_) => SqlTypeClass::Array,
+        }
+    }
+}
+
+/// The class of a [`SqlType`], within which values can be compared or
+/// assigned to each other: numbers, strings, dates and times, and so on.
+///
+/// See [`SqlExpression::CLASS`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlTypeClass {
+    /// Compatible with every class, as for `NULL` or raw SQL.
+    Unknown,
+    Numeric,
+    Textual,
+    Temporal,
+    Boolean,
+    Uuid,
+    Json,
+    Binary,
+    Array,
+}
+
+impl SqlTypeClass {
+    /// Whether values of both classes can be compared or assigned to each
+    /// other, which is always the case when any of them is unknown.
+    pub const fn is_compatible_with(self, other: SqlTypeClass) -> bool {
+        matches!(self, SqlTypeClass::Unknown)
+            || matches!(other, SqlTypeClass::Unknown)
+            || self as u8 == other as u8
+    }
+
+    /// The class of a list holding values of both classes, as in `IN (...)`.
+    pub(crate) const fn unify(self, other: SqlTypeClass) -> SqlTypeClass {
+        if !self.is_compatible_with(other) {
+            panic!("list values must have compatible SQL types");
+        }
+
+        match self {
+            SqlTypeClass::Unknown => other,
+            _ => self,
+        }
+    }
+}
+
+/// The default value of a [`Column`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnDefault {
+    Null,
+    Bool(bool),
+    Int(i64),
+    Str(&'static str),
+    CurrentTimestamp,
+    CurrentDate,
+    /// A raw SQL expression, written as is.
+    Expr(&'static str),
+}
+
+impl const SqlExpression for ColumnDefault {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        match *self {
+            ColumnDefault::Null => Null.write_sql_expression(sql),
+            ColumnDefault::Bool(value) => value.write_sql_expression(sql),
+            ColumnDefault::Int(value) => value.write_sql_expression(sql),
+            ColumnDefault::Str(value) => value.write_sql_expression(sql),
+            ColumnDefault::CurrentTimestamp => {
+                sql.push_str("CURRENT_TIMESTAMP");
+            }
+            // SQL Server has no `CURRENT_DATE`.
+            ColumnDefault::CurrentDate if matches!(sql.dialect(), Dialect::SqlServer) => {
+                sql.push_str("CAST(CURRENT_TIMESTAMP AS DATE)");
+            }
+            ColumnDefault::CurrentDate => {
+                sql.push_str("CURRENT_DATE");
+            }
+            ColumnDefault::Expr(expr) => {
+                sql.push_str(expr);
+            }
+        }
     }
 }
 
+/// How the values of a [`Column`] are generated by the database.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Generated {
+    /// `GENERATED ALWAYS AS IDENTITY`, rejecting explicit values.
+    AlwaysAsIdentity,
+    /// `GENERATED BY DEFAULT AS IDENTITY`, accepting explicit values.
+    ByDefaultAsIdentity,
+    /// Computed from a raw SQL expression on write.
+    Stored(&'static str),
+    /// Computed from a raw SQL expression on read.
+    Virtual(&'static str),
+}
+
+impl Generated {
+    pub const fn is_identity(&self) -> bool {
+        matches!(
+            self,
+            Generated::AlwaysAsIdentity | Generated::ByDefaultAsIdentity
+        )
+    }
+}
+
+/// A list of columns rendered by their bare names, as in `INSERT INTO table
+/// (a,b,c)`.
+#[const_trait]
+pub trait ColumnNames {
+    /// How many columns are listed, or `None` when it isn't known from the
+    /// type, as with slices.
+    const ARITY: Option<usize> = None;
+
+    /// The class of each listed column, see [`SqlExpression::CLASSES`].
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
+    fn write_column_names(&self, sql: &mut Sql);
+
+    /// Whether all the listed columns are columns of `table`.
+    fn belong_to(&self, table: &Table) -> bool;
+
+    /// The listed column, when there's exactly one.
+    fn single_column(&self) -> Option<Column>;
+}
+
+impl<const C: SqlTypeClass, const NULLABLE: bool> const ColumnNames for Column<C, NULLABLE> {
+    const ARITY: Option<usize> = Some(1);
+    const CLASSES: &'static [SqlTypeClass] = &[C];
+
+    fn write_column_names(&self, sql: &mut Sql) {
+        sql.push_ident(self.name);
+    }
+
+    fn belong_to(&self, table: &Table) -> bool {
+        str_eq(self.table.ident.schema, table.ident.schema)
+            && str_eq(self.table.ident.name, table.ident.name)
+    }
+
+    fn single_column(&self) -> Option<Column> {
+        Some(self.untyped())
+    }
+}
+
+/// No column list, as in `INSERT INTO table VALUES (...)`, filling all the
+/// columns of the table.
+impl const ColumnNames for () {
+    #[inline(always)]
+    fn write_column_names(&self, _: &mut Sql) {}
+
+    fn belong_to(&self, _: &Table) -> bool {
+        true
+    }
+
+    fn single_column(&self) -> Option<Column> {
+        None
+    }
+}
+
+impl<C: ~const ColumnNames, const N: usize> const ColumnNames for [C; N] {
+    const ARITY: Option<usize> = match C::ARITY {
+        Some(arity) => Some(arity * N),
+        None => None,
+    };
+
+    #[inline(always)]
+    fn write_column_names(&self, sql: &mut Sql) {
+        (self as &[C]).write_column_names(sql)
+    }
+
+    fn belong_to(&self, table: &Table) -> bool {
+        (self as &[C]).belong_to(table)
+    }
+
+    fn single_column(&self) -> Option<Column> {
+        (self as &[C]).single_column()
+    }
+}
+
+impl<C: ~const ColumnNames> const ColumnNames for &[C] {
+    fn write_column_names(&self, sql: &mut Sql) {
+        let mut columns = *self;
+
+        while let Some((cur, rest)) = columns.split_first() {
+            cur.write_column_names(sql);
+
+            if !rest.is_empty() {
+                sql.comma();
+            }
+
+            columns = rest;
+        }
+    }
+
+    fn belong_to(&self, table: &Table) -> bool {
+        let mut columns = *self;
+
+        while let Some((cur, rest)) = columns.split_first() {
+            if !cur.belong_to(table) {
+                return false;
+            }
+
+            columns = rest;
+        }
+
+        true
+    }
+
+    fn single_column(&self) -> Option<Column> {
+        match self {
+            [column] => column.single_column(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! impl_column_names_tuples {
+    (( $($x: ident,)+ )) => {
+        impl< $($x),+ > const ColumnNames for ($($x,)+)
+        where
+            $($x: ~const ColumnNames,)+
+        {
+            const ARITY: Option<usize> = {
+                let arity = Some(0);
+                $(let arity = add_arities(arity, $x::ARITY);)+
+                arity
+            };
+            const CLASSES: &'static [SqlTypeClass] = &[$(single_class($x::CLASSES)),+];
+
+            fn write_column_names(&self, sql: &mut Sql) {
+                let total = ${count(x)};
+
+                $(
+                    let val: &$x = &self.${index()};
+                    val.write_column_names(sql);
+
+                    if ${index()} != total - 1 {
+                        sql.comma();
+                    }
+                )+
+            }
+
+            fn belong_to(&self, table: &Table) -> bool {
+                $(
+                    let val: &$x = &self.${index()};
+                    if !val.belong_to(table) {
+                        return false;
+                    }
+                )+
+
+                true
+            }
+
+            fn single_column(&self) -> Option<Column> {
+                let total = ${count(x)};
+
+                $(
+                    if total == 1 {
+                        let val: &$x = &self.${index()};
+                        return val.single_column();
+                    }
+                )+
+
+                None
+            }
+        }
+    };
+}
+
+impl_column_names_tuples!((T1,));
+impl_column_names_tuples!((T1, T2,));
+impl_column_names_tuples!((T1, T2, T3,));
+impl_column_names_tuples!((T1, T2, T3, T4,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7, T8,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11,));
+impl_column_names_tuples!((T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,));
+
+/// A table, or anything shaped like one.
+///
+/// Tables with an empty schema are rendered by name alone, which is how common
+/// table expressions are declared, see [`with`](crate::query::with::with).
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Table {
     pub ident: Ident,
@@ -36,8 +472,373 @@ pub const fn table_columns(table: Table) -> &'static [Column] {
     table.all_columns
 }
 
+impl Table {
+    /// Refers to the table as `alias`, as in `schema.table AS alias`.
+    ///
+    /// Needed to join a table with itself.
+    pub const fn alias(self, alias: &'static str) -> AliasedTable {
+        AliasedTable { table: self, alias }
+    }
+}
+
 impl const SqlExpression for Table {
     fn write_sql_expression(&self, sql: &mut Sql) {
         self.ident.write_sql_expression(sql);
     }
 }
+
+/// Declares a table and its columns.
+///
+/// ```ignore
+/// table! {
+///     /// The registered users.
+///     pub users in public {
+///         id: BigInt.not_null().generated(AlwaysAsIdentity),
+///         name: Varchar(100).not_null(),
+///         email = "emailAddress": Text,
+///         created_at: TimestampTz.not_null().default(CurrentTimestamp),
+///     }
+/// }
+///
+/// from(users).select((users::id, users::email))
+/// ```
+///
+/// Expands to a `users` [`Table`] and a `users` module with one [`Column`] per
+/// column, typed with the [`SqlTypeClass`] of its type. Tables and columns are
+/// named as declared, without the `r#` of raw identifiers, unless given a name
+/// as in `email = "emailAddress"` or `pub users = "Users" in public`. Tables
+/// without `in schema` are rendered by name alone.
+///
+/// Attributes of the table, such as its documentation, are applied to the
+/// [`Table`] only.
+///
+/// Types are [`SqlType`] variants, followed by calls to the [`Column`] builder
+/// methods. [`ColumnDefault`] and [`Generated`] variants can be used
+/// unqualified as their arguments.
+///
+/// Columns refer to their table through `super`, so the macro must be used at
+/// module level.
+#[macro_export]
+macro_rules! table {
+    (
+        $(#[$attr: meta])*
+        $vis: vis $table: ident $(= $table_name: literal)? $(in $schema: ident)? {
+            $(
+                $(#[$col_attr: meta])*
+                $col: ident $(= $col_name: literal)?:
+                $ty: ident $(($($ty_arg: tt)*))?
+                $(.$modifier: ident($($modifier_arg: expr),*))*
+            ),+ $(,)?
+        }
+    ) => {
+        $(#[$attr])*
+        #[allow(non_upper_case_globals)]
+        $vis static $table: $crate::schema::Table = $crate::schema::Table {
+            ident: $crate::schema::Ident {
+                name: $crate::table!(@name $table $($table_name)?),
+                schema: $crate::table!(@schema $($schema)?),
+            },
+            all_columns: &[$($table::$col.untyped()),+],
+        };
+
+        #[allow(non_upper_case_globals)]
+        $vis mod $table {
+            $(
+                $(#[$col_attr])*
+                pub static $col: $crate::schema::Column<
+                    { $crate::table!(@type $ty $(($($ty_arg)*))?).class() },
+                    { $crate::table!(@nullable $($modifier)*) },
+                > = $crate::schema::Column::new(
+                    $crate::table!(@name $col $($col_name)?),
+                    &super::$table,
+                    $crate::table!(@type $ty $(($($ty_arg)*))?),
+                )
+                $(.$modifier($({
+                    #[allow(unused_imports)]
+                    use $crate::schema::{ColumnDefault::*, Generated::*};
+                    $modifier_arg
+                }),*))*;
+            )+
+        }
+    };
+    (@type $($ty: tt)+) => {{
+        #[allow(unused_imports)]
+        use $crate::schema::SqlType::*;
+        $($ty)+
+    }};
+    (@nullable) => { true };
+    (@nullable not_null $($modifier: ident)*) => { false };
+    (@nullable $other: ident $($modifier: ident)*) => { $crate::table!(@nullable $($modifier)*) };
+    (@schema) => { "" };
+    (@schema $schema: ident) => { $crate::schema::unraw(stringify!($schema)) };
+    (@name $ident: ident) => { $crate::schema::unraw(stringify!($ident)) };
+    (@name $ident: ident $name: literal) => { $name };
+}
+
+/// `ident` without the `r#` prefix of raw identifiers, as given by
+/// `stringify!(r#type)`.
+#[doc(hidden)]
+pub const fn unraw(ident: &'static str) -> &'static str {
+    let bytes = ident.as_bytes();
+
+    if bytes.len() < 2 || bytes[0] != b'r' || bytes[1] != b'#' {
+        return ident;
+    }
+
+    unsafe {
+        std::str::from_utf8_unchecked(std::slice::from_raw_parts(
+            bytes.as_ptr().add(2),
+            bytes.len() - 2,
+        ))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliasedTable {
+    pub table: Table,
+    pub alias: &'static str,
+}
+
+impl AliasedTable {
+    /// `column` of the table, qualified by the alias.
+    ///
+    /// Panics if `column` belongs to another table.
+    pub const fn column<const C: SqlTypeClass, const NULLABLE: bool>(
+        &self,
+        column: Column<C, NULLABLE>,
+    ) -> AliasedColumn<C, NULLABLE> {
+        let (owner, table) = (&column.table.ident, &self.table.ident);
+
+        if !str_eq(owner.name, table.name) || !str_eq(owner.schema, table.schema) {
+            panic!("the column doesn't belong to the aliased table");
+        }
+
+        AliasedColumn {
+            column,
+            alias: self.alias,
+        }
+    }
+
+    /// All the columns of the table, qualified by the alias.
+    pub const fn columns(&self) -> AliasedColumns {
+        AliasedColumns {
+            columns: self.table.all_columns,
+            alias: self.alias,
+        }
+    }
+}
+
+impl const SqlExpression for AliasedTable {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.table.write_sql_expression(sql);
+        sql.push_str(" AS ").push_ident(self.alias);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliasedColumn<
+    const C: SqlTypeClass = { SqlTypeClass::Unknown },
+    const NULLABLE: bool = true,
+> {
+    pub column: Column<C, NULLABLE>,
+    pub alias: &'static str,
+}
+
+impl<const C: SqlTypeClass, const NULLABLE: bool> const SqlExpression
+    for AliasedColumn<C, NULLABLE>
+{
+    const CLASS: SqlTypeClass = C;
+    const NULLABLE: bool = NULLABLE;
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        sql.push_ident(self.alias)
+            .dot()
+            .push_ident(self.column.name);
+    }
+}
+
+impl<const C: SqlTypeClass, const NULLABLE: bool> const ColumnNames for AliasedColumn<C, NULLABLE> {
+    const ARITY: Option<usize> = Some(1);
+    const CLASSES: &'static [SqlTypeClass] = &[C];
+
+    fn write_column_names(&self, sql: &mut Sql) {
+        self.column.write_column_names(sql);
+    }
+
+    fn belong_to(&self, table: &Table) -> bool {
+        self.column.belong_to(table)
+    }
+
+    fn single_column(&self) -> Option<Column> {
+        self.column.single_column()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AliasedColumns {
+    pub columns: &'static [Column],
+    pub alias: &'static str,
+}
+
+impl const SqlExpression for AliasedColumns {
+    const ARITY: Option<usize> = None;
+    const CLASSES: &'static [SqlTypeClass] = &[];
+
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        let mut columns = self.columns;
+
+        while let Some((cur, rest)) = columns.split_first() {
+            sql.push_ident(self.alias).dot().push_ident(cur.name);
+
+            if !rest.is_empty() {
+                sql.comma();
+            }
+
+            columns = rest;
+        }
+    }
+
+    fn arity(&self) -> Option<usize> {
+        Some(self.columns.len())
+    }
+}
+
+/// Columns rendered by their bare names, as needed to refer to the output
+/// columns of a [`Compound`](crate::query::compound::Compound) query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Unqualified<C>(pub C);
+
+impl<C: ~const ColumnNames> const SqlExpression for Unqualified<C> {
+    fn write_sql_expression(&self, sql: &mut Sql) {
+        self.0.write_column_names(sql);
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod fixtures {
+    use super::*;
+
+    pub(crate) type NumericColumn<const NULLABLE: bool = false> =
+        Column<{ SqlTypeClass::Numeric }, NULLABLE>;
+    pub(crate) type TextColumn<const NULLABLE: bool = false> =
+        Column<{ SqlTypeClass::Textual }, NULLABLE>;
+
+    pub(crate) static USERS: Table = Table {
+        ident: Ident {
+            name: "users",
+            schema: "public",
+        },
+        all_columns: &[
+            USERS_ID.untyped(),
+            USERS_NAME.untyped(),
+            USERS_EMAIL.untyped(),
+        ],
+    };
+    pub(crate) static USERS_ID: NumericColumn =
+        Column::new("id", &USERS, SqlType::BigInt).not_null();
+    pub(crate) static USERS_NAME: TextColumn =
+        Column::new("name", &USERS, SqlType::Text).not_null();
+    pub(crate) static USERS_EMAIL: TextColumn<true> = Column::new("email", &USERS, SqlType::Text);
+
+    pub(crate) static POSTS: Table = Table {
+        ident: Ident {
+            name: "posts",
+            schema: "public",
+        },
+        all_columns: &[
+            POSTS_ID.untyped(),
+            POSTS_USER_ID.untyped(),
+            POSTS_TITLE.untyped(),
+        ],
+    };
+    pub(crate) static POSTS_ID: NumericColumn =
+        Column::new("id", &POSTS, SqlType::BigInt).not_null();
+    pub(crate) static POSTS_USER_ID: NumericColumn =
+        Column::new("user_id", &POSTS, SqlType::BigInt).not_null();
+    pub(crate) static POSTS_TITLE: TextColumn<true> = Column::new("title", &POSTS, SqlType::Text);
+
+    pub(crate) static EMPLOYEES: Table = Table {
+        ident: Ident {
+            name: "employees",
+            schema: "hr",
+        },
+        all_columns: &[
+            EMPLOYEES_ID.untyped(),
+            EMPLOYEES_NAME.untyped(),
+            EMPLOYEES_MANAGER_ID.untyped(),
+        ],
+    };
+    pub(crate) static EMPLOYEES_ID: NumericColumn =
+        Column::new("id", &EMPLOYEES, SqlType::BigInt).not_null();
+    pub(crate) static EMPLOYEES_NAME: TextColumn =
+        Column::new("name", &EMPLOYEES, SqlType::Text).not_null();
+    pub(crate) static EMPLOYEES_MANAGER_ID: NumericColumn<true> =
+        Column::new("manager_id", &EMPLOYEES, SqlType::BigInt);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        expression::{ops::SqlExpressionExt, param::Param},
+        query::select::from,
+    };
+
+    crate::table! {
+        accounts in billing {
+            id: BigInt.not_null().generated(AlwaysAsIdentity),
+            owner_id = "ownerId": BigInt.not_null(),
+            balance: Numeric(12, 2).not_null().default(Int(0)),
+            tags: Array(&Text),
+            opened_at: TimestampTz.default(CurrentTimestamp).not_null(),
+        }
+    }
+
+    crate::table! {
+        totals {
+            owner_id: BigInt,
+            total: Numeric(12, 2),
+        }
+    }
+
+    crate::table! {
+        /// Renamed and with a raw identifier column.
+        r#events = "Events" in r#audit {
+            r#type: Text.not_null(),
+        }
+    }
+
+    #[test]
+    fn test_table_macro() {
+        static SQL: &str = from(accounts)
+            .inner_join(totals)
+            .on(accounts::owner_id.eq(totals::owner_id))
+            .select((accounts::id, totals::total))
+            .filter(accounts::balance.gt(Param::<i64>::new()))
+            .to_sql()
+            .into_str();
+
+        let _: &Column<{ SqlTypeClass::Numeric }, false> = &accounts::balance;
+        let _: &Column<{ SqlTypeClass::Array }, true> = &accounts::tags;
+        let _: &Column<{ SqlTypeClass::Temporal }, false> = &accounts::opened_at;
+
+        assert_eq!(accounts.all_columns.len(), 5);
+        assert_eq!(accounts::owner_id.name, "ownerId");
+        assert_eq!(accounts::owner_id.ty, SqlType::BigInt);
+        assert!(!accounts::owner_id.nullable && accounts::tags.nullable);
+        assert_eq!(accounts::id.generated, Some(Generated::AlwaysAsIdentity));
+        assert_eq!(accounts::balance.default, Some(ColumnDefault::Int(0)));
+        assert_eq!(accounts::tags.ty, SqlType::Array(&SqlType::Text));
+        assert_eq!(accounts::owner_id.table.ident.name, "accounts");
+        assert_eq!(totals.ident.schema, "");
+        assert_eq!(r#events.ident.name, "Events");
+        assert_eq!(r#events.ident.schema, "audit");
+        assert_eq!(r#events::r#type.name, "type");
+        assert_eq!(
+            SQL,
+            "SELECT accounts.id,totals.total FROM billing.accounts \
+             INNER JOIN totals ON accounts.\"ownerId\" = totals.owner_id \
+             WHERE accounts.balance > $1"
+        );
+    }
+}
@@ -0,0 +1,13 @@
+use const_sql_query_builder::{ddl::create_table, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+fn main() {
+    let _ = create_table(users).foreign_key((users::id, users::name), users, users::id);
+}
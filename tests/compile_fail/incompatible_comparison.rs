@@ -0,0 +1,16 @@
+use const_sql_query_builder::{
+    expression::{ops::SqlExpressionExt, param::Param},
+    table,
+};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+fn main() {
+    let _ = users::id.eq(Param::<String>::new());
+}
@@ -0,0 +1,15 @@
+use const_sql_query_builder::{expression::param::Param, query::insert::insert_into, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+fn main() {
+    let _ = insert_into(users)
+        .columns((users::id, users::name))
+        .values((Param::<i64>::new(),));
+}
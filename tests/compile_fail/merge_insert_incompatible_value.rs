@@ -0,0 +1,24 @@
+use const_sql_query_builder::{query::merge::merge_into, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+table! {
+    employees {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+    }
+}
+
+fn main() {
+    let _ = merge_into(users)
+        .using(employees)
+        .on(true)
+        .when_not_matched()
+        .then_insert((users::id, users::name), (employees::name, employees::id));
+}
@@ -0,0 +1,17 @@
+use const_sql_query_builder::{expression::SqlExpression, query::merge::merge_into, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+    }
+}
+
+table! {
+    staging {
+        id: BigInt.not_null(),
+    }
+}
+
+fn main() {
+    merge_into(users).using(staging).on(true).to_sql();
+}
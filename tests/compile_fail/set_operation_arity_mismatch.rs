@@ -0,0 +1,15 @@
+use const_sql_query_builder::{query::select::from, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+fn main() {
+    let _ = from(users)
+        .select((users::id, users::name))
+        .union(from(users).select(users::id));
+}
@@ -0,0 +1,13 @@
+use const_sql_query_builder::{expression::param::Param, query::update::update, table};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+fn main() {
+    let _ = update(users).set(users::name, Param::<Option<String>>::new());
+}
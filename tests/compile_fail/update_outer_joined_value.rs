@@ -0,0 +1,32 @@
+use const_sql_query_builder::{
+    expression::ops::SqlExpressionExt,
+    query::{select::from, update::update},
+    table,
+};
+
+table! {
+    users {
+        id: BigInt.not_null(),
+        name: Text.not_null(),
+        created_at: TimestampTz.not_null(),
+    }
+}
+
+table! {
+    posts {
+        id: BigInt.not_null(),
+        user_id: BigInt.not_null(),
+    }
+}
+
+fn main() {
+    let latest = from(users)
+        .left_join(posts)
+        .on(posts::user_id.eq(users::id))
+        .select(posts::id)
+        .alias("latest");
+
+    let _ = update(users)
+        .set(users::id, latest.column("id"))
+        .from(latest);
+}